@@ -0,0 +1,125 @@
+use std::{error::Error, io, pin::Pin};
+
+use futures_core::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{
+    JSONKey, JSONParseError, JSONReadError, JSONStreamParser, ParseOptions, RootJSONValue,
+};
+
+/// Size of the buffer [`parse_async_reader`] reads into
+const CHUNK: usize = 8 * 1024;
+
+/// [`crate::parse_reader`] for [`AsyncRead`] sources. `cb` returning `true` stops reading
+///
+/// # Errors
+/// Returns an error if reading fails or the input is invalid JSON
+pub async fn parse_async_reader(
+    reader: impl AsyncRead + Unpin,
+    cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+) -> Result<(), JSONReadError> {
+    parse_async_reader_with_options(reader, ParseOptions::default(), cb).await
+}
+
+/// [`parse_async_reader`] with a choice of which extensions to accept
+///
+/// # Errors
+/// Returns an error if reading fails or the input is invalid under `options`
+pub async fn parse_async_reader_with_options(
+    mut reader: impl AsyncRead + Unpin,
+    options: ParseOptions,
+    cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+) -> Result<(), JSONReadError> {
+    let mut parser = JSONStreamParser::with_options(options, cb);
+    let mut buffer = vec![0; CHUNK];
+    let mut at = 0;
+    while !parser.is_finished() {
+        let length = match reader.read(&mut buffer).await {
+            Ok(0) => break,
+            Ok(length) => length,
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(JSONReadError::IO { at, error }),
+        };
+        parser.feed(&buffer[..length])?;
+        at += length;
+    }
+    Ok(parser.finish()?)
+}
+
+/// Parses chunks (for example `bytes::Bytes` from a request body) as they arrive. `cb` returning
+/// `true` stops polling `stream`
+///
+/// # Errors
+/// Returns an error if the input is invalid JSON
+pub async fn parse_stream<T: AsRef<[u8]>>(
+    stream: impl Stream<Item = T> + Unpin,
+    cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+) -> Result<(), JSONParseError> {
+    parse_stream_with_options(stream, ParseOptions::default(), cb).await
+}
+
+/// [`parse_stream`] with a choice of which extensions to accept
+///
+/// # Errors
+/// Returns an error if the input is invalid under `options`
+pub async fn parse_stream_with_options<T: AsRef<[u8]>>(
+    mut stream: impl Stream<Item = T> + Unpin,
+    options: ParseOptions,
+    cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+) -> Result<(), JSONParseError> {
+    let mut parser = JSONStreamParser::with_options(options, cb);
+    while !parser.is_finished() {
+        let next = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await;
+        let Some(chunk) = next else {
+            break;
+        };
+        parser.feed(chunk.as_ref())?;
+    }
+    parser.finish()
+}
+
+/// [`parse_stream`] for streams of results, such as HTTP response bodies. An error from `stream`
+/// stops parsing and is returned as [`JSONReadError::IO`], wrapped in a [`std::io::Error`] if it
+/// is not one
+///
+/// # Errors
+/// Returns an error if `stream` yields one or the input is invalid JSON
+pub async fn try_parse_stream<T: AsRef<[u8]>, E: Into<Box<dyn Error + Send + Sync>>>(
+    stream: impl Stream<Item = Result<T, E>> + Unpin,
+    cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+) -> Result<(), JSONReadError> {
+    try_parse_stream_with_options(stream, ParseOptions::default(), cb).await
+}
+
+/// [`try_parse_stream`] with a choice of which extensions to accept
+///
+/// # Errors
+/// Returns an error if `stream` yields one or the input is invalid under `options`
+pub async fn try_parse_stream_with_options<
+    T: AsRef<[u8]>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+>(
+    mut stream: impl Stream<Item = Result<T, E>> + Unpin,
+    options: ParseOptions,
+    cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+) -> Result<(), JSONReadError> {
+    let mut parser = JSONStreamParser::with_options(options, cb);
+    let mut at = 0;
+    while !parser.is_finished() {
+        let next = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await;
+        let chunk = match next {
+            Some(Ok(chunk)) => chunk,
+            Some(Err(error)) => {
+                let error = match error.into().downcast::<io::Error>() {
+                    Ok(error) => *error,
+                    Err(error) => io::Error::other(error),
+                };
+                return Err(JSONReadError::IO { at, error });
+            }
+            None => break,
+        };
+        parser.feed(chunk.as_ref())?;
+        at += chunk.as_ref().len();
+    }
+    Ok(parser.finish()?)
+}
@@ -0,0 +1,407 @@
+//! Throughput and allocations of parsing generated inputs, against `serde_json` as a baseline.
+//!
+//! ```sh
+//! cargo bench --bench parse [--features simd] [-- <corpus name>]
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Write,
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use simple_json_parser::{
+    parse, parse_bytes, parse_events, parse_with_options, JSONIterator, KeyArray, ParseOptions,
+    Tape,
+};
+
+/// Counts calls to `alloc` and `realloc` so each parser can report how often it allocates
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        // SAFETY: forwarded as is
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded as is
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(new_size.saturating_sub(layout.size()), Ordering::Relaxed);
+        // SAFETY: forwarded as is
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// How long each parser is run on each corpus for
+const DURATION: Duration = Duration::from_millis(500);
+
+/// Deeper than the nesting of any corpus. `serde_json` stops at 128
+const KEY_ARRAY_SIZE: usize = 128;
+
+/// Deterministic so that runs are comparable
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        // xorshift64
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, end: u64) -> u64 {
+        self.next() % end
+    }
+
+    fn pick<'a>(&mut self, from: &[&'a str]) -> &'a str {
+        from[usize::try_from(self.below(from.len() as u64)).unwrap()]
+    }
+}
+
+const WORDS: &[&str] = &[
+    "parser", "request", "value", "token", "compile", "cache", "user", "session", "config",
+    "timeout", "retry", "stream", "buffer", "schema", "index", "query", "worker", "queue",
+];
+
+/// Approximate size of each corpus in bytes
+const SIZE: usize = 4 * 1024 * 1024;
+
+/// `package.json` like configuration files, in an array
+fn configs(random: &mut Random) -> String {
+    let mut out = String::from("[\n");
+    let mut idx = 0;
+    while out.len() < SIZE {
+        if idx > 0 {
+            out.push_str(",\n");
+        }
+        let name = random.pick(WORDS);
+        write!(
+            out,
+            r#"  {{
+    "name": "@scope/{name}-{idx}",
+    "version": "{}.{}.{}",
+    "description": "A {} {} for {} written in Rust",
+    "license": "MIT",
+    "private": {},
+    "main": "./dist/index.mjs",
+    "type": "module",
+    "scripts": {{
+      "build": "cargo build --lib --target wasm32-unknown-unknown && npm run bind",
+      "test": "npm run build && node test.mjs",
+      "lint": "eslint . --ext .ts"
+    }},
+    "keywords": ["{}", "{}", "{}"],
+    "dependencies": {{
+      "{}": "^{}.{}.0",
+      "{}": "~{}.{}.{}"
+    }},
+    "build": {{
+      "failOnWarn": false,
+      "entries": [{{ "builder": "rollup", "input": "./src/index" }}],
+      "rollup": {{ "commonjs": true, "esbuild": {{ "target": "esnext", "minify": true }} }}
+    }}
+  }}"#,
+            random.below(10),
+            random.below(40),
+            random.below(100),
+            random.pick(WORDS),
+            random.pick(WORDS),
+            random.pick(WORDS),
+            random.below(2) == 0,
+            random.pick(WORDS),
+            random.pick(WORDS),
+            random.pick(WORDS),
+            random.pick(WORDS),
+            random.below(20),
+            random.below(20),
+            random.pick(WORDS),
+            random.below(5),
+            random.below(20),
+            random.below(100),
+        )
+        .unwrap();
+        idx += 1;
+    }
+    out.push_str("\n]\n");
+    out
+}
+
+/// Rows of integers, decimals and exponents
+fn numbers(random: &mut Random) -> String {
+    let mut out = String::from("[");
+    while out.len() < SIZE {
+        if out.len() > 1 {
+            out.push_str(",\n");
+        }
+        out.push('[');
+        for column in 0..8 {
+            if column > 0 {
+                out.push_str(", ");
+            }
+            let sign = if random.below(4) == 0 { "-" } else { "" };
+            match column % 4 {
+                0 => write!(out, "{sign}{}", random.below(1000)),
+                1 => write!(out, "{sign}{}", random.next() >> 12),
+                2 => write!(
+                    out,
+                    "{sign}{}.{:06}",
+                    random.below(10_000),
+                    random.below(1_000_000)
+                ),
+                _ => write!(
+                    out,
+                    "{sign}{}.{}e-{}",
+                    random.below(10),
+                    random.below(1000),
+                    random.below(300)
+                ),
+            }
+            .unwrap();
+        }
+        out.push(']');
+    }
+    out.push_str("]\n");
+    out
+}
+
+/// Log lines with long messages, some containing escapes and non-ASCII characters
+fn logs(random: &mut Random) -> String {
+    const LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
+    let mut out = String::from("[\n");
+    while out.len() < SIZE {
+        if out.len() > 2 {
+            out.push_str(",\n");
+        }
+        let mut message = String::new();
+        for _ in 0..random.below(40) + 10 {
+            match random.below(30) {
+                0 => message.push_str("\\\"quoted\\\" "),
+                1 => message.push_str("line\\nbreak "),
+                2 => message.push_str("caf\u{e9} \u{1f680} "),
+                3 => message.push_str("\\u00e9t\\u00e9 "),
+                _ => {
+                    message.push_str(random.pick(WORDS));
+                    message.push(' ');
+                }
+            }
+        }
+        write!(
+            out,
+            r#"{{"timestamp": "2024-06-{:02}T{:02}:{:02}:{:02}.{:03}Z", "level": "{}", "target": "server::{}", "message": "{}", "request": {{"method": "GET", "path": "/api/{}/{}", "status": {}, "duration_ms": {}.{}}}}}"#,
+            random.below(28) + 1,
+            random.below(24),
+            random.below(60),
+            random.below(60),
+            random.below(1000),
+            random.pick(LEVELS),
+            random.pick(WORDS),
+            message.trim_end(),
+            random.pick(WORDS),
+            random.below(100_000),
+            [200, 201, 304, 404, 500][usize::try_from(random.below(5)).unwrap()],
+            random.below(500),
+            random.below(100),
+        )
+        .unwrap();
+    }
+    out.push_str("\n]\n");
+    out
+}
+
+/// Objects and arrays within each other, 100 deep
+fn nested(random: &mut Random) -> String {
+    const DEPTH: usize = 100;
+    let mut out = String::from("[");
+    while out.len() < SIZE {
+        if out.len() > 1 {
+            out.push(',');
+        }
+        let mut closing = Vec::with_capacity(DEPTH);
+        for level in 0..DEPTH {
+            if level % 2 == 0 {
+                write!(
+                    out,
+                    r#"{{"{}": {}, "next": "#,
+                    random.pick(WORDS),
+                    random.below(100)
+                )
+                .unwrap();
+                closing.push('}');
+            } else {
+                write!(out, "[{}, ", random.below(2) == 0).unwrap();
+                closing.push(']');
+            }
+        }
+        out.push_str("null");
+        out.extend(closing.into_iter().rev());
+    }
+    out.push_str("]\n");
+    out
+}
+
+type Generator = fn(&mut Random) -> String;
+
+type Parser = fn(&str) -> usize;
+
+/// Each returns the number of values, so that the work is not optimised away
+const PARSERS: &[(&str, Parser)] = &[
+    ("parse", |on| {
+        let mut count = 0;
+        parse(on, |keys, value| {
+            black_box((keys, value));
+            count += 1;
+        })
+        .unwrap();
+        count
+    }),
+    ("parse (strict)", |on| {
+        let mut count = 0;
+        parse_with_options(on, ParseOptions::strict(), |keys, value| {
+            black_box((keys, value));
+            count += 1;
+        })
+        .unwrap();
+        count
+    }),
+    ("parse (simd: false)", |on| {
+        let options = ParseOptions {
+            simd: false,
+            ..ParseOptions::default()
+        };
+        let mut count = 0;
+        parse_with_options(on, options, |keys, value| {
+            black_box((keys, value));
+            count += 1;
+        })
+        .unwrap();
+        count
+    }),
+    ("parse_bytes", |on| {
+        let mut count = 0;
+        parse_bytes(on.as_bytes(), ParseOptions::default(), |keys, value| {
+            black_box((keys, value));
+            count += 1;
+        })
+        .unwrap();
+        count
+    }),
+    ("parse_events", |on| {
+        let mut count = 0;
+        parse_events(on, |keys, event| {
+            black_box((keys, event));
+            count += 1;
+        })
+        .unwrap();
+        count
+    }),
+    ("JSONIterator + KeyArray", |on| {
+        let keys = KeyArray::<KEY_ARRAY_SIZE>::new();
+        let mut iterator = JSONIterator::with_key_chain(on, ParseOptions::default(), keys);
+        let mut count = 0;
+        while let Some(value) = iterator.next_value() {
+            black_box((iterator.key_chain(), value.unwrap()));
+            count += 1;
+        }
+        count
+    }),
+    ("JSONIterator as Iterator", |on| {
+        JSONIterator::new(on)
+            .inspect(|item| {
+                black_box(item.as_ref().unwrap());
+            })
+            .count()
+    }),
+    ("Tape::parse", |on| Tape::parse(on).unwrap().nodes().len()),
+    ("serde_json::Value (baseline)", |on| {
+        let value: serde_json::Value = serde_json::from_str(on).unwrap();
+        black_box(value).is_array().into()
+    }),
+    ("str::from_utf8 (lower bound)", |on| {
+        black_box(core::str::from_utf8(black_box(on.as_bytes())).unwrap()).len()
+    }),
+];
+
+struct Measurement {
+    megabytes_per_second: f64,
+    allocations: usize,
+    allocated: usize,
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn measure(parser: Parser, on: &str) -> Measurement {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    black_box(parser(black_box(on)));
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    let allocated = ALLOCATED.load(Ordering::Relaxed) - allocated;
+
+    let mut runs = 0;
+    let start = Instant::now();
+    while start.elapsed() < DURATION {
+        black_box(parser(black_box(on)));
+        runs += 1;
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    Measurement {
+        megabytes_per_second: (on.len() * runs) as f64 / elapsed / 1_000_000.0,
+        allocations,
+        allocated,
+    }
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`
+    let filter: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+
+    let corpora: [(&str, Generator); 4] = [
+        ("configs", configs),
+        ("numbers", numbers),
+        ("logs", logs),
+        ("nested", nested),
+    ];
+
+    println!(
+        "simd feature: {}",
+        if cfg!(feature = "simd") { "on" } else { "off" }
+    );
+    for (name, generate) in corpora {
+        if !filter.is_empty() && !filter.iter().any(|filter| name.contains(filter.as_str())) {
+            continue;
+        }
+        let on = generate(&mut Random(0x2545_f491_4f6c_dd1d));
+        println!("\n{name} ({} KiB)", on.len() / 1024);
+        println!(
+            "  {:<30} {:>10} {:>12} {:>14}",
+            "", "MB/s", "allocations", "bytes allocated"
+        );
+        for (parser_name, parser) in PARSERS {
+            let Measurement {
+                megabytes_per_second,
+                allocations,
+                allocated,
+            } = measure(*parser, &on);
+            println!(
+                "  {parser_name:<30} {megabytes_per_second:>10.1} {allocations:>12} {allocated:>14}"
+            );
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use alloc::{borrow::Cow, vec::Vec};
+use core::fmt;
+
+use crate::{
+    unescape, CommentKind, JSONEvent, JSONIterator, JSONKey, JSONParseError, ParseOptions,
+    RootJSONValue, Span,
+};
+
+/// Every token in a document, including whitespace, comments and punctuation, so that writing it
+/// with [`fmt::Display`] gives back the input exactly. Tokens can be replaced or moved to edit the
+/// document while keeping its formatting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxTree<'a> {
+    /// The root value along with whitespace and comments before and after it
+    pub children: Vec<SyntaxNode<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyntaxNode<'a> {
+    Token(SyntaxToken<'a>),
+    /// From the `{` token to the `}` token
+    Object(Vec<SyntaxNode<'a>>),
+    /// From the `[` token to the `]` token
+    Array(Vec<SyntaxNode<'a>>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxToken<'a> {
+    pub kind: TokenKind,
+    /// As it appears in the input, for example with quotes and escapes for strings and keys
+    pub text: Cow<'a, str>,
+    /// Where the token was in the input
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Whitespace,
+    Comment(CommentKind),
+    OpenBrace,
+    CloseBrace,
+    OpenBracket,
+    CloseBracket,
+    Colon,
+    Comma,
+    Key,
+    String,
+    /// Including JSON5 `Infinity` and `NaN`
+    Number,
+    True,
+    False,
+    Null,
+    /// Characters that are ignored outside of strict mode, for example between members of objects
+    Skipped,
+}
+
+impl TokenKind {
+    /// Whitespace, comments and skipped characters
+    #[must_use]
+    pub fn is_trivia(self) -> bool {
+        matches!(
+            self,
+            TokenKind::Whitespace | TokenKind::Comment(_) | TokenKind::Skipped
+        )
+    }
+}
+
+impl<'a> SyntaxTree<'a> {
+    /// # Errors
+    /// Returns an error if `on` is not valid JSON or has no value
+    pub fn parse(on: &'a str) -> Result<Self, JSONParseError> {
+        Self::parse_with_options(on, ParseOptions::default())
+    }
+
+    /// [`SyntaxTree::parse`] with a choice of which extensions to accept. Stops at the first error,
+    /// even with [`ParseOptions::recover`]
+    ///
+    /// # Errors
+    /// Returns an error if `on` is invalid under `options` or has no value
+    pub fn parse_with_options(
+        on: &'a str,
+        mut options: ParseOptions,
+    ) -> Result<Self, JSONParseError> {
+        options.comment_events = true;
+        let mut iterator = JSONIterator::with_options(on, options);
+        // Tokens reported by the iterator. The gaps between them are whitespace and punctuation
+        let mut tokens: Vec<(Span, TokenKind)> = Vec::new();
+        while let Some(event) = iterator.next_event() {
+            let kind = match event? {
+                JSONEvent::ObjectStart => TokenKind::OpenBrace,
+                JSONEvent::ObjectEnd => TokenKind::CloseBrace,
+                JSONEvent::ArrayStart => TokenKind::OpenBracket,
+                JSONEvent::ArrayEnd => TokenKind::CloseBracket,
+                JSONEvent::Value(RootJSONValue::String(_)) => TokenKind::String,
+                JSONEvent::Value(RootJSONValue::Number(_)) => TokenKind::Number,
+                JSONEvent::Value(RootJSONValue::True) => TokenKind::True,
+                JSONEvent::Value(RootJSONValue::False) => TokenKind::False,
+                JSONEvent::Value(RootJSONValue::Null) => TokenKind::Null,
+                JSONEvent::Comment(comment) => TokenKind::Comment(comment.kind),
+            };
+            if let (
+                TokenKind::OpenBrace
+                | TokenKind::OpenBracket
+                | TokenKind::String
+                | TokenKind::Number
+                | TokenKind::True
+                | TokenKind::False
+                | TokenKind::Null,
+                Some(JSONKey::Slice(_)),
+                Some(&key),
+            ) = (
+                kind,
+                iterator.key_chain().last(),
+                iterator.key_spans().last(),
+            ) {
+                // Keys are not events, so go before comments between the key and the value
+                let at = tokens
+                    .iter()
+                    .rposition(|(span, _)| span.start < key.start)
+                    .map_or(0, |idx| idx + 1);
+                tokens.insert(at, (key, TokenKind::Key));
+            }
+            tokens.push((iterator.span(), kind));
+        }
+        iterator.expect_value()?;
+
+        let mut children = Vec::new();
+        // Objects and arrays that have been started but not ended
+        let mut parents: Vec<Vec<SyntaxNode<'a>>> = Vec::new();
+        let mut last = 0;
+        for (span, kind) in tokens {
+            push_gap(on, last, span.start, &mut children);
+            last = span.end;
+            let token = SyntaxNode::Token(SyntaxToken {
+                kind,
+                text: Cow::Borrowed(&on[span.start..span.end]),
+                span,
+            });
+            match kind {
+                TokenKind::OpenBrace | TokenKind::OpenBracket => {
+                    parents.push(core::mem::take(&mut children));
+                    children.push(token);
+                }
+                TokenKind::CloseBrace | TokenKind::CloseBracket => {
+                    children.push(token);
+                    let inner =
+                        core::mem::replace(&mut children, parents.pop().unwrap_or_default());
+                    children.push(if let TokenKind::CloseBrace = kind {
+                        SyntaxNode::Object(inner)
+                    } else {
+                        SyntaxNode::Array(inner)
+                    });
+                }
+                _ => children.push(token),
+            }
+        }
+        push_gap(on, last, on.len(), &mut children);
+        Ok(Self { children })
+    }
+
+    #[must_use]
+    pub fn root(&self) -> Option<&SyntaxNode<'a>> {
+        self.children.iter().find(|node| node.is_value())
+    }
+
+    pub fn root_mut(&mut self) -> Option<&mut SyntaxNode<'a>> {
+        self.children.iter_mut().find(|node| node.is_value())
+    }
+
+    /// All tokens in order
+    pub fn tokens(&self) -> impl Iterator<Item = &SyntaxToken<'a>> {
+        self.children.iter().flat_map(SyntaxNode::tokens)
+    }
+}
+
+impl fmt::Display for SyntaxTree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tokens().try_for_each(|token| f.write_str(&token.text))
+    }
+}
+
+/// Splits the text between two reported tokens into whitespace, punctuation and skipped characters
+fn push_gap<'a>(on: &'a str, start: usize, end: usize, children: &mut Vec<SyntaxNode<'a>>) {
+    let mut run: Option<(usize, TokenKind)> = None;
+    for (idx, chr) in on[start..end].char_indices() {
+        let idx = start + idx;
+        let kind = match chr {
+            ',' => TokenKind::Comma,
+            ':' => TokenKind::Colon,
+            chr if chr.is_whitespace() || chr == '\u{feff}' => TokenKind::Whitespace,
+            _ => TokenKind::Skipped,
+        };
+        match run {
+            // Whitespace and skipped characters are grouped
+            Some((_, run_kind))
+                if run_kind == kind
+                    && matches!(kind, TokenKind::Whitespace | TokenKind::Skipped) => {}
+            _ => {
+                if let Some((run_start, run_kind)) = run {
+                    children.push(token(on, run_start, idx, run_kind));
+                }
+                run = Some((idx, kind));
+            }
+        }
+    }
+    if let Some((run_start, run_kind)) = run {
+        children.push(token(on, run_start, end, run_kind));
+    }
+}
+
+fn token(on: &str, start: usize, end: usize, kind: TokenKind) -> SyntaxNode<'_> {
+    SyntaxNode::Token(SyntaxToken {
+        kind,
+        text: Cow::Borrowed(&on[start..end]),
+        span: Span { start, end },
+    })
+}
+
+impl<'a> SyntaxNode<'a> {
+    /// Objects, arrays and value tokens
+    #[must_use]
+    pub fn is_value(&self) -> bool {
+        match self {
+            SyntaxNode::Token(token) => matches!(
+                token.kind,
+                TokenKind::String
+                    | TokenKind::Number
+                    | TokenKind::True
+                    | TokenKind::False
+                    | TokenKind::Null
+            ),
+            SyntaxNode::Object(_) | SyntaxNode::Array(_) => true,
+        }
+    }
+
+    #[must_use]
+    pub fn as_token(&self) -> Option<&SyntaxToken<'a>> {
+        match self {
+            SyntaxNode::Token(token) => Some(token),
+            SyntaxNode::Object(_) | SyntaxNode::Array(_) => None,
+        }
+    }
+
+    /// The tokens of an object or array, including its brackets
+    #[must_use]
+    pub fn children(&self) -> Option<&[SyntaxNode<'a>]> {
+        match self {
+            SyntaxNode::Object(children) | SyntaxNode::Array(children) => Some(children),
+            SyntaxNode::Token(_) => None,
+        }
+    }
+
+    /// See [`SyntaxNode::children`]
+    pub fn children_mut(&mut self) -> Option<&mut Vec<SyntaxNode<'a>>> {
+        match self {
+            SyntaxNode::Object(children) | SyntaxNode::Array(children) => Some(children),
+            SyntaxNode::Token(_) => None,
+        }
+    }
+
+    /// All tokens in order
+    pub fn tokens(&self) -> impl Iterator<Item = &SyntaxToken<'a>> {
+        let mut stack = alloc::vec![self];
+        core::iter::from_fn(move || loop {
+            let node = stack.pop()?;
+            match node {
+                SyntaxNode::Token(token) => return Some(token),
+                SyntaxNode::Object(children) | SyntaxNode::Array(children) => {
+                    stack.extend(children.iter().rev());
+                }
+            }
+        })
+    }
+
+    /// From the start of the first token to the end of the last, in the input
+    #[must_use]
+    pub fn span(&self) -> Span {
+        let mut tokens = self.tokens();
+        let start = tokens.next().map_or(0, |token| token.span.start);
+        let end = tokens.last().map_or(start, |token| token.span.end);
+        Span { start, end }
+    }
+
+    /// Members of an object, with their keys unescaped
+    #[must_use]
+    pub fn members(&self) -> Option<impl Iterator<Item = (Cow<'_, str>, &SyntaxNode<'a>)>> {
+        let SyntaxNode::Object(children) = self else {
+            return None;
+        };
+        Some(
+            member_indices(children)
+                .map(|(key, value)| (key_name(&children[key]), &children[value])),
+        )
+    }
+
+    /// Items of an array
+    #[must_use]
+    pub fn items(&self) -> Option<impl Iterator<Item = &SyntaxNode<'a>>> {
+        let SyntaxNode::Array(children) = self else {
+            return None;
+        };
+        Some(children.iter().filter(|node| node.is_value()))
+    }
+
+    /// The value of the last member named `key`, if an object
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&SyntaxNode<'a>> {
+        self.members()?
+            .filter(|(name, _)| name == key)
+            .last()
+            .map(|(_, value)| value)
+    }
+
+    /// See [`SyntaxNode::get`]
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut SyntaxNode<'a>> {
+        let SyntaxNode::Object(children) = self else {
+            return None;
+        };
+        let (_, value) = member_indices(children)
+            .filter(|(name, _)| key_name(&children[*name]) == key)
+            .last()?;
+        children.get_mut(value)
+    }
+
+    /// The item at `index`, if an array
+    #[must_use]
+    pub fn index(&self, index: usize) -> Option<&SyntaxNode<'a>> {
+        self.items()?.nth(index)
+    }
+
+    /// See [`SyntaxNode::index`]
+    pub fn index_mut(&mut self, index: usize) -> Option<&mut SyntaxNode<'a>> {
+        let SyntaxNode::Array(children) = self else {
+            return None;
+        };
+        children
+            .iter_mut()
+            .filter(|node| node.is_value())
+            .nth(index)
+    }
+
+    /// The value of a token, with strings as the raw contents between the quotes. `None` for
+    /// objects and arrays
+    #[must_use]
+    pub fn value(&self) -> Option<RootJSONValue<'_>> {
+        let token = self.as_token()?;
+        match token.kind {
+            TokenKind::String => Some(RootJSONValue::String(without_quotes(&token.text))),
+            TokenKind::Number => Some(RootJSONValue::Number(&token.text)),
+            TokenKind::True => Some(RootJSONValue::True),
+            TokenKind::False => Some(RootJSONValue::False),
+            TokenKind::Null => Some(RootJSONValue::Null),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SyntaxNode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tokens().try_for_each(|token| f.write_str(&token.text))
+    }
+}
+
+/// Indexes of each key token and the value after it
+fn member_indices<'n>(children: &'n [SyntaxNode<'_>]) -> impl Iterator<Item = (usize, usize)> + 'n {
+    let mut key = None;
+    children
+        .iter()
+        .enumerate()
+        .filter_map(move |(idx, node)| match node {
+            SyntaxNode::Token(SyntaxToken {
+                kind: TokenKind::Key,
+                ..
+            }) => {
+                key = Some(idx);
+                None
+            }
+            node if node.is_value() => key.take().map(|key| (key, idx)),
+            _ => None,
+        })
+}
+
+fn key_name<'n>(node: &'n SyntaxNode<'_>) -> Cow<'n, str> {
+    node.as_token().map_or(Cow::Borrowed(""), |token| {
+        unescape(without_quotes(&token.text))
+    })
+}
+
+/// JSON5 keys may be unquoted
+fn without_quotes(text: &str) -> &str {
+    match text.as_bytes().first() {
+        Some(b'"' | b'\'') if text.len() >= 2 => &text[1..text.len() - 1],
+        _ => text,
+    }
+}
@@ -0,0 +1,494 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+};
+use core::fmt;
+
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+use crate::{
+    unescape, JSONEvent, JSONIterator, JSONKey, JSONParseError, JSONParseErrorReason, ParseOptions,
+    RootJSONValue,
+};
+
+/// Error from [`from_str`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JSONDeserializeError {
+    Parse(JSONParseError),
+    /// From the type being deserialized, for example a missing field or a value of the wrong type.
+    /// `at` is the start of the last value read
+    Custom {
+        at: usize,
+        message: String,
+    },
+}
+
+impl JSONDeserializeError {
+    /// Byte offset in the input
+    #[must_use]
+    pub fn at(&self) -> usize {
+        match self {
+            JSONDeserializeError::Parse(error) => error.at,
+            JSONDeserializeError::Custom { at, .. } => *at,
+        }
+    }
+}
+
+impl core::error::Error for JSONDeserializeError {}
+
+impl fmt::Display for JSONDeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JSONDeserializeError::Parse(error) => fmt::Display::fmt(error, f),
+            JSONDeserializeError::Custom { at, message } => {
+                f.write_fmt(format_args!("JSONDeserializeError: {message} at {at:?}"))
+            }
+        }
+    }
+}
+
+impl From<JSONParseError> for JSONDeserializeError {
+    fn from(error: JSONParseError) -> Self {
+        JSONDeserializeError::Parse(error)
+    }
+}
+
+/// Set to the position of the value being deserialized by [`Deserializer::locate`]
+const UNKNOWN: usize = usize::MAX;
+
+impl de::Error for JSONDeserializeError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        JSONDeserializeError::Custom {
+            at: UNKNOWN,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Deserializes an instance of `T` from `on`, accepting comments and trailing commas. `&str` fields
+/// borrow from `on` if they do not contain escapes
+///
+/// # Errors
+/// Returns an error if `on` is not valid JSON or does not match `T`
+pub fn from_str<'de, T: de::Deserialize<'de>>(on: &'de str) -> Result<T, JSONDeserializeError> {
+    from_str_with_options(on, ParseOptions::default())
+}
+
+/// [`from_str`] with a choice of which extensions to accept
+///
+/// # Errors
+/// Returns an error if `on` is invalid under `options` or does not match `T`
+pub fn from_str_with_options<'de, T: de::Deserialize<'de>>(
+    on: &'de str,
+    options: ParseOptions,
+) -> Result<T, JSONDeserializeError> {
+    let mut deserializer = Deserializer::with_options(on, options);
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Used when [`ParseOptions::max_depth`] is not set, as deserializing recurses into each object and
+/// array
+const MAX_DEPTH: usize = 128;
+
+/// [`serde::Deserializer`] reading from a [`JSONIterator`]
+pub struct Deserializer<'de> {
+    on: &'de str,
+    iterator: JSONIterator<'de>,
+    /// The next event, when it has been looked at but not used
+    peeked: Option<JSONEvent<'de>>,
+}
+
+impl<'de> Deserializer<'de> {
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(on: &'de str) -> Self {
+        Self::with_options(on, ParseOptions::default())
+    }
+
+    /// Without [`ParseOptions::max_depth`], objects and arrays more than 128 deep are a
+    /// [`JSONParseErrorReason::MaxDepthExceeded`] error rather than overflowing the stack
+    #[must_use]
+    pub fn with_options(on: &'de str, options: ParseOptions) -> Self {
+        let options = ParseOptions {
+            max_depth: options.max_depth.or(Some(MAX_DEPTH)),
+            ..options
+        };
+        Self {
+            on,
+            iterator: JSONIterator::with_options(on, options),
+            peeked: None,
+        }
+    }
+
+    /// Checks the rest of the input, after the value
+    ///
+    /// # Errors
+    /// Returns an error if the rest of the input is invalid
+    pub fn end(&mut self) -> Result<(), JSONDeserializeError> {
+        match self.next()? {
+            None => Ok(()),
+            Some(_) => Err(self.error(JSONParseErrorReason::ExpectedEndOfValue)),
+        }
+    }
+
+    /// Skips comments. `None` at the end of the input
+    fn peek(&mut self) -> Result<Option<&JSONEvent<'de>>, JSONDeserializeError> {
+        if self.peeked.is_none() {
+            self.peeked = loop {
+                match self.iterator.next_event() {
+                    Some(Ok(JSONEvent::Comment(_))) => {}
+                    Some(event) => break Some(event?),
+                    None => break None,
+                }
+            };
+        }
+        Ok(self.peeked.as_ref())
+    }
+
+    fn next(&mut self) -> Result<Option<JSONEvent<'de>>, JSONDeserializeError> {
+        self.peek()?;
+        Ok(self.peeked.take())
+    }
+
+    /// Errors at the end of the input, as the iterator does not report a missing value outside of
+    /// strict mode
+    fn next_value(&mut self) -> Result<JSONEvent<'de>, JSONDeserializeError> {
+        self.next()?
+            .ok_or(JSONDeserializeError::Parse(JSONParseError {
+                at: self.on.len(),
+                reason: JSONParseErrorReason::ExpectedValue,
+            }))
+    }
+
+    /// The key of the next member, for [`de::MapAccess`]
+    fn key(&self) -> Option<&'de str> {
+        match self.iterator.key_chain().last() {
+            Some(JSONKey::Slice(key)) => Some(key),
+            _ => None,
+        }
+    }
+
+    fn error(&self, reason: JSONParseErrorReason) -> JSONDeserializeError {
+        JSONDeserializeError::Parse(JSONParseError {
+            at: self.iterator.span().start,
+            reason,
+        })
+    }
+
+    /// Adds the position to errors from [`de::Error::custom`]
+    fn locate(&self, mut error: JSONDeserializeError) -> JSONDeserializeError {
+        if let JSONDeserializeError::Custom { ref mut at, .. } = error {
+            if *at == UNKNOWN {
+                *at = self.iterator.span().start;
+            }
+        }
+        error
+    }
+
+    /// For when a visitor does not read all members or items
+    fn end_of(&mut self, end: &JSONEvent<'de>) -> Result<(), JSONDeserializeError> {
+        if self.next_value()? == *end {
+            Ok(())
+        } else {
+            Err(self.error(JSONParseErrorReason::ExpectedBracket))
+        }
+    }
+
+    fn any<V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value, JSONDeserializeError> {
+        match self.next_value()? {
+            JSONEvent::ObjectStart => {
+                let mut members = Members {
+                    deserializer: self,
+                    ended: false,
+                };
+                let value = visitor.visit_map(&mut members)?;
+                if !members.ended {
+                    self.end_of(&JSONEvent::ObjectEnd)?;
+                }
+                Ok(value)
+            }
+            JSONEvent::ArrayStart => {
+                // Visitors for tuples stop after their length
+                let mut items = Items {
+                    deserializer: self,
+                    ended: false,
+                };
+                let value = visitor.visit_seq(&mut items)?;
+                if !items.ended {
+                    self.end_of(&JSONEvent::ArrayEnd)?;
+                }
+                Ok(value)
+            }
+            JSONEvent::Value(RootJSONValue::String(value)) => visit_str(value, visitor),
+            JSONEvent::Value(value @ RootJSONValue::Number(number)) => {
+                // `-0` is left to `f64`, which keeps the sign
+                let negative = number.starts_with('-');
+                if let (false, Some(value)) = (negative, value.as_u64()) {
+                    visitor.visit_u64(value)
+                } else if let (true, Some(value @ ..=-1)) = (negative, value.as_i64()) {
+                    visitor.visit_i64(value)
+                } else if let Some(value) = value.as_f64() {
+                    visitor.visit_f64(value)
+                } else {
+                    // For example JSON5 hexadecimal numbers too large for a `u64`
+                    visitor.visit_borrowed_str(number)
+                }
+            }
+            JSONEvent::Value(RootJSONValue::True) => visitor.visit_bool(true),
+            JSONEvent::Value(RootJSONValue::False) => visitor.visit_bool(false),
+            JSONEvent::Value(RootJSONValue::Null) => visitor.visit_unit(),
+            JSONEvent::ObjectEnd | JSONEvent::ArrayEnd | JSONEvent::Comment(_) => {
+                Err(self.error(JSONParseErrorReason::ExpectedValue))
+            }
+        }
+    }
+}
+
+/// Zero copy when there are no escapes
+fn visit_str<'de, V: Visitor<'de>>(
+    value: &'de str,
+    visitor: V,
+) -> Result<V::Value, JSONDeserializeError> {
+    match unescape(value) {
+        Cow::Borrowed(value) => visitor.visit_borrowed_str(value),
+        Cow::Owned(value) => visitor.visit_string(value),
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = JSONDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.any(visitor).map_err(|error| self.locate(error))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if let Some(JSONEvent::Value(RootJSONValue::Null)) = self.peek()? {
+            self.peeked = None;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(&mut *self)
+        }
+        .map_err(|error| self.locate(error))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor
+            .visit_newtype_struct(&mut *self)
+            .map_err(|error| self.locate(error))
+    }
+
+    /// Unit variants are strings, others are objects with a single member named after the variant
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let result = match self.peek()? {
+            Some(JSONEvent::Value(RootJSONValue::String(variant))) => {
+                let variant = unescape(variant);
+                self.peeked = None;
+                visitor.visit_enum(variant.into_deserializer())
+            }
+            Some(JSONEvent::ObjectStart) => {
+                self.peeked = None;
+                let value = visitor.visit_enum(Variant(&mut *self))?;
+                self.end_of(&JSONEvent::ObjectEnd)?;
+                Ok(value)
+            }
+            _ => self.any(visitor),
+        };
+        result.map_err(|error| self.locate(error))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let mut depth = 0usize;
+        loop {
+            match self.next_value()? {
+                JSONEvent::ObjectStart | JSONEvent::ArrayStart => depth += 1,
+                JSONEvent::ObjectEnd | JSONEvent::ArrayEnd => depth -= 1,
+                JSONEvent::Value(_) | JSONEvent::Comment(_) => {}
+            }
+            if depth == 0 {
+                return visitor.visit_unit();
+            }
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+/// [`de::MapAccess`] for the members of an object
+struct Members<'d, 'de> {
+    deserializer: &'d mut Deserializer<'de>,
+    ended: bool,
+}
+
+impl<'de> de::MapAccess<'de> for Members<'_, 'de> {
+    type Error = JSONDeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.deserializer.peek()? {
+            Some(JSONEvent::ObjectEnd) => {
+                self.deserializer.peeked = None;
+                self.ended = true;
+                Ok(None)
+            }
+            // The key chain is up to date, as the value has been read
+            Some(_) => match self.deserializer.key() {
+                Some(key) => seed.deserialize(Key(key)).map(Some),
+                None => Err(self.deserializer.error(JSONParseErrorReason::ExpectedQuote)),
+            },
+            None => Err(self
+                .deserializer
+                .error(JSONParseErrorReason::ExpectedBracket)),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+}
+
+/// [`de::SeqAccess`] for the items of an array
+struct Items<'d, 'de> {
+    deserializer: &'d mut Deserializer<'de>,
+    ended: bool,
+}
+
+impl<'de> de::SeqAccess<'de> for Items<'_, 'de> {
+    type Error = JSONDeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.deserializer.peek()? {
+            Some(JSONEvent::ArrayEnd) => {
+                self.deserializer.peeked = None;
+                self.ended = true;
+                Ok(None)
+            }
+            Some(_) => seed.deserialize(&mut *self.deserializer).map(Some),
+            None => Err(self
+                .deserializer
+                .error(JSONParseErrorReason::ExpectedBracket)),
+        }
+    }
+}
+
+/// Object keys, which borrow from the input when they do not contain escapes
+struct Key<'de>(&'de str);
+
+/// Numbers are parsed from the key, so that maps can have integer keys like with `serde_json`
+macro_rules! deserialize_number_keys {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                let key = unescape(self.0);
+                match key.parse::<$ty>() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(de::Error::invalid_value(de::Unexpected::Str(&key), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Key<'de> {
+    type Error = JSONDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visit_str(self.0, visitor)
+    }
+
+    deserialize_number_keys! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool char str string bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// [`de::EnumAccess`] for `{ "Variant": ... }`
+struct Variant<'d, 'de>(&'d mut Deserializer<'de>);
+
+impl<'de> de::EnumAccess<'de> for Variant<'_, 'de> {
+    type Error = JSONDeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), Self::Error> {
+        match self.0.peek()? {
+            Some(JSONEvent::ObjectEnd) | None => {
+                Err(self.0.error(JSONParseErrorReason::ExpectedQuote))
+            }
+            Some(_) => match self.0.key() {
+                Some(key) => Ok((seed.deserialize(Key(key))?, self)),
+                None => Err(self.0.error(JSONParseErrorReason::ExpectedQuote)),
+            },
+        }
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Variant<'_, 'de> {
+    type Error = JSONDeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(self.0)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.0)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(self.0, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(self.0, visitor)
+    }
+}
@@ -0,0 +1,143 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::{JSONParseError, JSONParseErrorReason};
+
+/// One-based line and column. Columns count characters, not bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Resolves a byte offset (for example [`JSONParseError::at`]) in `on` to a line and column.
+/// Offsets past the end resolve to the end of the input
+#[must_use]
+pub fn line_column(on: &str, at: usize) -> LineColumn {
+    let at = floor_char_boundary(on, at);
+    let before = &on[..at];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    LineColumn {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+fn floor_char_boundary(on: &str, at: usize) -> usize {
+    let mut at = at.min(on.len());
+    while !on.is_char_boundary(at) {
+        at -= 1;
+    }
+    at
+}
+
+impl JSONParseErrorReason {
+    /// What went wrong, for people editing the input
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            JSONParseErrorReason::ExpectedColon => "missing `:` between key and value",
+            JSONParseErrorReason::ExpectedEndOfValue => "unexpected character after value",
+            JSONParseErrorReason::ExpectedBracket => "unclosed object or array",
+            JSONParseErrorReason::ExpectedTrueFalseNull => "invalid literal",
+            JSONParseErrorReason::ExpectedValue => "expected a value",
+            JSONParseErrorReason::ExpectedEndOfMultilineComment => "unterminated block comment",
+            JSONParseErrorReason::ExpectedQuote => "expected a string",
+            JSONParseErrorReason::InvalidEscape => "invalid escape sequence",
+            JSONParseErrorReason::InvalidNumber => "invalid number",
+            JSONParseErrorReason::TrailingComma => "trailing comma",
+            JSONParseErrorReason::ControlCharacterInString => {
+                "unescaped control character in string"
+            }
+            JSONParseErrorReason::InvalidUTF8 => "invalid UTF-8",
+            JSONParseErrorReason::KeyChainFull => "nested too deeply for the key chain",
+            JSONParseErrorReason::MaxDepthExceeded => "nested too deeply",
+            JSONParseErrorReason::MaxStringLengthExceeded => "string too long",
+            JSONParseErrorReason::MaxNumberLengthExceeded => "number too long",
+            JSONParseErrorReason::MaxValuesExceeded => "too many values",
+        }
+    }
+
+    /// What could have been written instead
+    #[must_use]
+    pub fn expected(self) -> &'static str {
+        match self {
+            JSONParseErrorReason::ExpectedColon => "`:`",
+            JSONParseErrorReason::ExpectedEndOfValue => "`,`, `}` or `]`",
+            JSONParseErrorReason::ExpectedBracket => "`}` or `]`",
+            JSONParseErrorReason::ExpectedTrueFalseNull => "`true`, `false` or `null`",
+            JSONParseErrorReason::ExpectedValue => {
+                "a string, number, object, array, `true`, `false` or `null`"
+            }
+            JSONParseErrorReason::ExpectedEndOfMultilineComment => "`*/`",
+            JSONParseErrorReason::ExpectedQuote => "`\"`",
+            JSONParseErrorReason::InvalidEscape => {
+                r#"one of `\"`, `\\`, `\/`, `\b`, `\f`, `\n`, `\r`, `\t` or `\uXXXX`"#
+            }
+            JSONParseErrorReason::InvalidNumber => "a digit",
+            JSONParseErrorReason::TrailingComma => "a value after `,`",
+            JSONParseErrorReason::ControlCharacterInString => "an escape sequence such as `\\n`",
+            JSONParseErrorReason::InvalidUTF8 => "UTF-8 encoded text",
+            JSONParseErrorReason::KeyChainFull => "less nesting, or a larger key chain",
+            JSONParseErrorReason::MaxDepthExceeded => "less nesting",
+            JSONParseErrorReason::MaxStringLengthExceeded => "a shorter string",
+            JSONParseErrorReason::MaxNumberLengthExceeded => "a shorter number",
+            JSONParseErrorReason::MaxValuesExceeded => "fewer values",
+        }
+    }
+}
+
+impl fmt::Display for JSONParseErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+impl JSONParseError {
+    /// Where the error is in `on`
+    #[must_use]
+    pub fn line_column(&self, on: &str) -> LineColumn {
+        line_column(on, self.at)
+    }
+
+    /// Renders the error with the line it occurred on. `on` should be the input that was parsed
+    #[must_use]
+    pub fn diagnostic<'a>(&'a self, on: &'a str) -> Diagnostic<'a> {
+        Diagnostic { error: self, on }
+    }
+}
+
+/// [`fmt::Display`]s an error along with the offending line and a caret under the position
+pub struct Diagnostic<'a> {
+    error: &'a JSONParseError,
+    on: &'a str,
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { error, on } = self;
+        let LineColumn { line, column } = error.line_column(on);
+
+        let at = floor_char_boundary(on, error.at);
+        let line_start = on[..at].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = on[at..].find('\n').map_or(on.len(), |idx| at + idx);
+        let source = on[line_start..line_end].trim_end_matches('\r');
+
+        // Keep tabs so the caret lines up
+        let padding = on[line_start..at]
+            .chars()
+            .map(|chr| if chr == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let gutter = " ".repeat(line.to_string().len());
+
+        writeln!(f, "error: {}", error.reason)?;
+        writeln!(f, "{gutter}--> {line}:{column}")?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line} | {source}")?;
+        write!(
+            f,
+            "{gutter} | {padding}^ expected {}",
+            error.reason.expected()
+        )
+    }
+}
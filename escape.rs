@@ -0,0 +1,179 @@
+use alloc::{borrow::Cow, string::String};
+
+/// Progress through an escape sequence in a key or string value
+#[derive(Clone, Copy)]
+pub(crate) enum Escape {
+    None,
+    /// After a `\`. `low_surrogate` is set when following a `\uD800-\uDBFF` high surrogate
+    Backslash {
+        low_surrogate: bool,
+    },
+    Unicode {
+        digits: u8,
+        value: u16,
+        low_surrogate: bool,
+    },
+    /// After a high surrogate, which must be directly followed by `\uDC00-\uDFFF`
+    ExpectingLowSurrogate,
+    /// JSON5 `\xHH`
+    Hex {
+        digits: u8,
+    },
+    /// JSON5 line continuation. A following `\n` is part of it
+    CarriageReturn,
+    /// JSON5 `\0`, which cannot be followed by a digit
+    Null,
+}
+
+impl Escape {
+    /// Returns `None` if `chr` is not valid at this point of an escape sequence. `json5` allows its
+    /// additional escapes
+    pub(crate) fn next(self, chr: char, json5: bool) -> Option<Self> {
+        match self {
+            Escape::CarriageReturn if chr == '\n' => Some(Escape::None),
+            Escape::Null if chr.is_ascii_digit() => None,
+            Escape::None | Escape::CarriageReturn | Escape::Null => Some(if chr == '\\' {
+                Escape::Backslash {
+                    low_surrogate: false,
+                }
+            } else {
+                Escape::None
+            }),
+            Escape::Backslash { low_surrogate } => match chr {
+                'u' => Some(Escape::Unicode {
+                    digits: 0,
+                    value: 0,
+                    low_surrogate,
+                }),
+                _ if low_surrogate => None,
+                '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't' => Some(Escape::None),
+                'x' if json5 => Some(Escape::Hex { digits: 0 }),
+                '\r' if json5 => Some(Escape::CarriageReturn),
+                '0' if json5 => Some(Escape::Null),
+                '1'..='9' => None,
+                // Any other character escapes to itself (including line terminators)
+                _ if json5 => Some(Escape::None),
+                _ => None,
+            },
+            Escape::Unicode {
+                digits,
+                value,
+                low_surrogate,
+            } => {
+                let digit = u16::try_from(chr.to_digit(16)?).ok()?;
+                let value = (value << 4) | digit;
+                if digits < 3 {
+                    Some(Escape::Unicode {
+                        digits: digits + 1,
+                        value,
+                        low_surrogate,
+                    })
+                } else {
+                    match (low_surrogate, value) {
+                        (false, 0xD800..=0xDBFF) => Some(Escape::ExpectingLowSurrogate),
+                        (true, 0xDC00..=0xDFFF) => Some(Escape::None),
+                        (false, 0xDC00..=0xDFFF) | (true, _) => None,
+                        (false, _) => Some(Escape::None),
+                    }
+                }
+            }
+            Escape::ExpectingLowSurrogate => (chr == '\\').then_some(Escape::Backslash {
+                low_surrogate: true,
+            }),
+            Escape::Hex { digits } => {
+                if !chr.is_ascii_hexdigit() {
+                    None
+                } else if digits == 0 {
+                    Some(Escape::Hex { digits: 1 })
+                } else {
+                    Some(Escape::None)
+                }
+            }
+        }
+    }
+
+    /// Whether a `"` here ends the string
+    pub(crate) fn can_end(self) -> bool {
+        matches!(self, Escape::None | Escape::CarriageReturn | Escape::Null)
+    }
+}
+
+/// Decodes the escape sequences in a key or string value (including JSON5 escapes). Only allocates
+/// if there are any
+///
+/// Expects the input to have been validated by the parser, unpaired surrogates are replaced with
+/// [`char::REPLACEMENT_CHARACTER`]
+#[must_use]
+pub fn unescape(on: &str) -> Cow<'_, str> {
+    let Some(first) = on.find('\\') else {
+        return Cow::Borrowed(on);
+    };
+
+    let mut result = String::with_capacity(on.len());
+    result.push_str(&on[..first]);
+
+    let mut chars = on[first..].chars();
+    while let Some(chr) = chars.next() {
+        if chr != '\\' {
+            result.push(chr);
+            continue;
+        }
+        match chars.next() {
+            Some('b') => result.push('\u{8}'),
+            Some('f') => result.push('\u{c}'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some('v') => result.push('\u{b}'),
+            Some('0') => result.push('\0'),
+            Some('x') => {
+                let code = chars
+                    .by_ref()
+                    .take(2)
+                    .try_fold(0, |acc, chr| Some((acc << 4) | chr.to_digit(16)?));
+                result.push(
+                    code.and_then(char::from_u32)
+                        .unwrap_or(char::REPLACEMENT_CHARACTER),
+                );
+            }
+            Some('\r') if chars.clone().next() == Some('\n') => {
+                chars.next();
+            }
+            Some('u') => {
+                let code = read_hex(&mut chars).map_or(0xFFFD, u32::from);
+                let code = if (0xD800..=0xDBFF).contains(&code) {
+                    let mut lookahead = chars.clone();
+                    let low = (lookahead.next() == Some('\\') && lookahead.next() == Some('u'))
+                        .then(|| read_hex(&mut lookahead))
+                        .flatten()
+                        .map(u32::from)
+                        .filter(|low| (0xDC00..=0xDFFF).contains(low));
+
+                    if let Some(low) = low {
+                        chars = lookahead;
+                        0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00)
+                    } else {
+                        code
+                    }
+                } else {
+                    code
+                };
+                result.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            // Line continuations are removed
+            Some('\r' | '\n' | '\u{2028}' | '\u{2029}') | None => {}
+            Some(chr) => result.push(chr),
+        }
+    }
+
+    Cow::Owned(result)
+}
+
+fn read_hex(chars: &mut core::str::Chars<'_>) -> Option<u16> {
+    let mut value = 0;
+    for _ in 0..4 {
+        let digit = u16::try_from(chars.next()?.to_digit(16)?).ok()?;
+        value = (value << 4) | digit;
+    }
+    Some(value)
+}
@@ -0,0 +1,23 @@
+use simple_json_parser::{format, minify, WriteOptions};
+
+/// Prints a file (or stdin) indented, keeping comments, or minified with `--minify`
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+    let minified = args.next_if(|arg| arg == "--minify").is_some();
+    let content = if let Some(path) = args.next() {
+        std::fs::read_to_string(path).unwrap()
+    } else {
+        std::io::read_to_string(std::io::stdin()).unwrap()
+    };
+
+    let result = if minified {
+        minify(&content)
+    } else {
+        format(&content, WriteOptions::pretty())
+    };
+
+    match result {
+        Ok(output) => println!("{output}"),
+        Err(error) => eprintln!("{}", error.diagnostic(&content)),
+    }
+}
@@ -0,0 +1,23 @@
+use std::io::BufReader;
+
+use simple_json_parser::{parse_reader, JSONKey, RootJSONValue};
+
+/// Prints the values in a file (or stdin) without reading it all into memory first
+fn main() {
+    let result = if let Some(path) = std::env::args().nth(1) {
+        let file = std::fs::File::open(path).unwrap();
+        parse_reader(BufReader::new(file), print)
+    } else {
+        parse_reader(std::io::stdin().lock(), print)
+    };
+
+    if let Err(error) = result {
+        eprintln!("{error}");
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn print(keys: &[JSONKey], value: RootJSONValue) -> bool {
+    eprintln!("{keys:?} -> {value:?}");
+    false
+}
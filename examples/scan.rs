@@ -1,4 +1,4 @@
-use simple_json_parser::{parse, JSONParseError};
+use simple_json_parser::parse;
 
 fn main() {
     let base = r#"{
@@ -89,7 +89,7 @@ fn main() {
 
     let result = parse(&content, |keys, value| eprintln!("{keys:?} -> {value:?}"));
 
-    if let Err(JSONParseError { at, reason }) = result {
-        eprintln!("{reason:?} @ {at}");
+    if let Err(error) = result {
+        eprintln!("{}", error.diagnostic(&content));
     }
 }
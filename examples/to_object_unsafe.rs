@@ -5,9 +5,6 @@ use std::collections::HashMap;
 use simple_json_parser::{parse, JSONKey, RootJSONValue};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let path = std::env::args().nth(1).ok_or("Expected first argument")?;
-    let content = std::fs::read_to_string(path)?;
-
     pub type Object = HashMap<String, Value>;
 
     #[derive(Debug)]
@@ -26,6 +23,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    let path = std::env::args().nth(1).ok_or("Expected first argument")?;
+    let content = std::fs::read_to_string(path)?;
+
     let mut root = Object::new();
 
     let _res = parse(&content, |keys, value| {
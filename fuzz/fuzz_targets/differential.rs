@@ -0,0 +1,160 @@
+//! In strict mode, accepts and rejects the same inputs as `serde_json` and finds the same leaf values
+
+#![no_main]
+
+use std::fmt;
+
+use libfuzzer_sys::fuzz_target;
+use serde::de::{DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use simple_json_parser::{parse_bytes, JSONKey, ParseOptions, RootJSONValue};
+use simple_json_parser_fuzz::Key;
+
+/// Numbers are compared as [`f64`]s, as `serde_json` does not keep their text
+#[derive(Debug, PartialEq)]
+enum Leaf {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+type Leaves = Vec<(Vec<Key>, Leaf)>;
+
+fuzz_target!(|data: &[u8]| {
+    let reference = reference(data);
+    // `serde_json` has a nesting limit of 128 and rejects numbers that overflow `f64`, while
+    // this crate has no limit and leaves numbers as text (see `tests/json_test_suite.rs`)
+    if let Err(ref error) = reference {
+        let error = error.to_string();
+        if error.starts_with("recursion limit exceeded") || error.starts_with("number out of range")
+        {
+            return;
+        }
+    }
+
+    let mut leaves = Vec::new();
+    let result = parse_bytes(data, ParseOptions::strict(), |keys, value| {
+        let leaf = match value {
+            RootJSONValue::String(_) => Leaf::String(value.as_str().unwrap().into_owned()),
+            RootJSONValue::Number(_) => Leaf::Number(value.as_f64().unwrap()),
+            RootJSONValue::True => Leaf::Bool(true),
+            RootJSONValue::False => Leaf::Bool(false),
+            RootJSONValue::Null => Leaf::Null,
+        };
+        let path = keys
+            .iter()
+            .map(|key| match key {
+                JSONKey::Slice(_) => Key::Slice(key.as_str().unwrap().into_owned()),
+                JSONKey::Index(index) => Key::Index(*index),
+            })
+            .collect();
+        leaves.push((path, leaf));
+    });
+
+    match (result, reference) {
+        (Ok(()), Ok(expected)) => assert_eq!(leaves, expected),
+        (Err(_), Err(_)) => {}
+        (result, reference) => {
+            panic!("parse_bytes: {result:?}, serde_json: {reference:?}")
+        }
+    }
+});
+
+fn reference(data: &[u8]) -> Result<Leaves, serde_json::Error> {
+    let mut leaves = Vec::new();
+    let mut deserializer = serde_json::Deserializer::from_slice(data);
+    Flatten {
+        path: &mut Vec::new(),
+        leaves: &mut leaves,
+    }
+    .deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(leaves)
+}
+
+/// Collects leaves in order, unlike `serde_json::Value` which removes duplicate keys
+struct Flatten<'a> {
+    path: &'a mut Vec<Key>,
+    leaves: &'a mut Leaves,
+}
+
+impl Flatten<'_> {
+    fn leaf(self, leaf: Leaf) {
+        self.leaves.push((self.path.clone(), leaf));
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Flatten<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Flatten<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        self.leaf(Leaf::Null);
+        Ok(())
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<(), E> {
+        self.leaf(Leaf::Bool(value));
+        Ok(())
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_i64<E>(self, value: i64) -> Result<(), E> {
+        self.leaf(Leaf::Number(value as f64));
+        Ok(())
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn visit_u64<E>(self, value: u64) -> Result<(), E> {
+        self.leaf(Leaf::Number(value as f64));
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<(), E> {
+        self.leaf(Leaf::Number(value));
+        Ok(())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<(), E> {
+        self.leaf(Leaf::String(value.to_owned()));
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        for index in 0.. {
+            self.path.push(Key::Index(index));
+            let item = seq.next_element_seed(Flatten {
+                path: &mut *self.path,
+                leaves: &mut *self.leaves,
+            })?;
+            self.path.pop();
+            if item.is_none() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            self.path.push(Key::Slice(key));
+            map.next_value_seed(Flatten {
+                path: &mut *self.path,
+                leaves: &mut *self.leaves,
+            })?;
+            self.path.pop();
+        }
+        Ok(())
+    }
+}
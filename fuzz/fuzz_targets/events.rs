@@ -0,0 +1,90 @@
+//! The pull and push APIs should not panic, and should agree with each other
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use simple_json_parser::{
+    JSONEvent, JSONIterator, JSONParseError, JSONStreamParser, KeyArray, ParseOptions,
+};
+use simple_json_parser_fuzz::{access, check_span, options, Key, Value};
+
+type Values = Vec<(Vec<Key>, Value)>;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&chunk_size, data)) = data.split_first() else {
+        return;
+    };
+    let (options, on) = options(data);
+
+    let mut iterator = JSONIterator::from_bytes(on, options);
+    while let Some(event) = iterator.next_event() {
+        let span = iterator.span();
+        check_span(on, span);
+        for span in iterator.key_spans() {
+            check_span(on, *span);
+        }
+        assert_eq!(iterator.key_chain().len(), iterator.key_spans().len());
+        match event {
+            Ok(JSONEvent::Value(value)) => access(iterator.key_chain(), &value),
+            Ok(JSONEvent::Comment(comment)) => check_span(on, comment.span),
+            Ok(_) => {}
+            Err(error) => assert!(error.at <= on.len(), "{error:?}"),
+        }
+    }
+
+    let Ok(on) = std::str::from_utf8(on) else {
+        return;
+    };
+
+    // Fixed size key chains error rather than overflow
+    let mut iterator = JSONIterator::with_key_chain(on, options, KeyArray::<4>::new());
+    while let Some(value) = iterator.next_value() {
+        if let Ok(value) = value {
+            access(iterator.key_chain(), &value);
+        }
+    }
+
+    // Feeding the input in chunks gives the same values and error. Recovering is skipped, as the
+    // stream parser stops at the end of each chunk with an error
+    let options = ParseOptions {
+        recover: false,
+        ..options
+    };
+    let expected = iterator_values(on, options);
+    let chunk_size = usize::from(chunk_size % 16) + 1;
+    assert_eq!(stream_values(on, options, chunk_size), expected);
+});
+
+fn iterator_values(on: &str, options: ParseOptions) -> (Values, Option<JSONParseError>) {
+    let mut values = Vec::new();
+    let mut iterator = JSONIterator::with_options(on, options);
+    while let Some(value) = iterator.next_value() {
+        match value {
+            Ok(value) => values.push((
+                iterator.key_chain().iter().map(Key::from).collect(),
+                Value::from(&value),
+            )),
+            Err(error) => return (values, Some(error)),
+        }
+    }
+    (values, None)
+}
+
+fn stream_values(
+    on: &str,
+    options: ParseOptions,
+    chunk_size: usize,
+) -> (Values, Option<JSONParseError>) {
+    let mut values = Vec::new();
+    let mut parser = JSONStreamParser::with_options(options, |keys, value| {
+        values.push((keys.iter().map(Key::from).collect(), Value::from(&value)));
+        false
+    });
+    for chunk in on.as_bytes().chunks(chunk_size) {
+        if let Err(error) = parser.feed(chunk) {
+            return (values, Some(error));
+        }
+    }
+    let error = parser.finish().err();
+    (values, error)
+}
@@ -0,0 +1,87 @@
+//! The callback APIs should return an error rather than panic on any input
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use simple_json_parser::{
+    format_with_options, parse_bytes, parse_bytes_raw, parse_with_options, parse_with_recovery,
+    parse_with_spans, unescape, ParseOptions, RawJSONValue, SyntaxTree, WriteOptions,
+};
+use simple_json_parser_fuzz::{access, check_span, options};
+
+fuzz_target!(|data: &[u8]| {
+    let (options, on) = options(data);
+
+    let checked = parse_bytes(on, options, |keys, value| access(keys, &value));
+    if let Err(ref error) = checked {
+        assert!(error.at <= on.len(), "{error:?}");
+    }
+
+    let raw = parse_bytes_raw(on, options, |keys, value| {
+        if let RawJSONValue::Other(value) = value {
+            access(keys, &value);
+        }
+    });
+    // Only string values are checked differently
+    if std::str::from_utf8(on).is_ok() {
+        assert_eq!(raw, checked);
+    }
+
+    let Ok(on) = std::str::from_utf8(on) else {
+        return;
+    };
+
+    let mut values = Vec::new();
+    let result = parse_with_options(on, options, |keys, value| {
+        access(keys, &value);
+        values.push(value);
+    });
+    if let Err(error) = result {
+        assert!(error.at <= on.len(), "{error:?}");
+        let _ = error.line_column(on);
+        let _ = error.diagnostic(on).to_string();
+    }
+
+    // Recovering does not change the values before the first error
+    let mut recovered = Vec::new();
+    let errors = parse_with_recovery(on, options, |_, value| recovered.push(value));
+    assert_eq!(recovered.get(..values.len()), Some(&values[..]));
+    for error in errors {
+        assert!(error.at <= on.len(), "{error:?}");
+        let _ = error.diagnostic(on).to_string();
+    }
+
+    let _ = parse_with_spans(on, |keys, key_spans, value, span| {
+        assert_eq!(keys.len(), key_spans.len());
+        for span in key_spans.iter().chain([&span]) {
+            check_span(on.as_bytes(), *span);
+            assert!(on.is_char_boundary(span.start) && on.is_char_boundary(span.end));
+        }
+        access(keys, &value);
+    });
+
+    let _ = unescape(on);
+
+    // Lossless
+    if let Ok(tree) = SyntaxTree::parse_with_options(on, options) {
+        assert_eq!(tree.to_string(), on);
+    }
+
+    // Formatting is stable
+    if let Ok(formatted) = format_with_options(on, options, WriteOptions::pretty()) {
+        // Re-escaping and writing hexadecimal numbers in decimal can make them longer
+        let options = ParseOptions {
+            max_string_length: None,
+            max_number_length: None,
+            ..options
+        };
+        let again = format_with_options(&formatted, options, WriteOptions::pretty());
+        assert_eq!(again.as_ref(), Ok(&formatted), "{on:?}");
+    }
+
+    // Formatted output is JSON, whatever the dialect of the input
+    if let Ok(formatted) = format_with_options(on, options, WriteOptions::compact()) {
+        let result = parse_with_options(&formatted, ParseOptions::strict(), |_, _| {});
+        assert!(result.is_ok(), "{on:?} {formatted:?} {result:?}");
+    }
+});
@@ -0,0 +1,86 @@
+//! Shared between the fuzz targets
+
+use simple_json_parser::{JSONKey, ParseOptions, RootJSONValue, Span};
+
+/// Takes [`ParseOptions`] from the first two bytes of `data`, so that the fuzzer explores every
+/// combination. Returns the rest of `data`
+#[must_use]
+pub fn options(data: &[u8]) -> (ParseOptions, &[u8]) {
+    let (flags, limits, rest) = match data {
+        [flags, limits, rest @ ..] => (*flags, *limits, rest),
+        _ => (0, 0, &[][..]),
+    };
+    let flag = |bit: u8| flags & (1 << bit) != 0;
+    let limit = |bit: u8| (limits & (1 << bit) != 0).then_some(usize::from(limits >> 4));
+    let options = ParseOptions {
+        comments: flag(0),
+        hash_comments: flag(1),
+        trailing_commas: flag(2),
+        strict: flag(3),
+        json5: flag(4),
+        comment_events: flag(5),
+        recover: flag(6),
+        simd: flag(7),
+        max_depth: limit(1),
+        max_string_length: limit(2),
+        max_number_length: limit(3),
+        max_values: None,
+    };
+    (options, rest)
+}
+
+/// A [`JSONKey`] that outlives the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    Slice(String),
+    Index(usize),
+}
+
+impl From<&JSONKey<'_>> for Key {
+    fn from(key: &JSONKey<'_>) -> Self {
+        match key {
+            JSONKey::Slice(key) => Key::Slice((*key).to_owned()),
+            JSONKey::Index(index) => Key::Index(*index),
+        }
+    }
+}
+
+/// A [`RootJSONValue`] that outlives the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Number(String),
+    True,
+    False,
+    Null,
+}
+
+impl From<&RootJSONValue<'_>> for Value {
+    fn from(value: &RootJSONValue<'_>) -> Self {
+        match value {
+            RootJSONValue::String(value) => Value::String((*value).to_owned()),
+            RootJSONValue::Number(value) => Value::Number((*value).to_owned()),
+            RootJSONValue::True => Value::True,
+            RootJSONValue::False => Value::False,
+            RootJSONValue::Null => Value::Null,
+        }
+    }
+}
+
+/// Calls every accessor on `value`, which should not panic
+pub fn access(keys: &[JSONKey<'_>], value: &RootJSONValue<'_>) {
+    for key in keys {
+        let _ = key.as_str();
+    }
+    let _ = value.as_str();
+    let _ = value.as_i64();
+    let _ = value.as_u64();
+    let _ = value.as_f64();
+    let _ = value.as_decimal();
+}
+
+/// Panics if `span` is not within `on`
+pub fn check_span(on: &[u8], span: Span) {
+    assert!(span.start <= span.end, "{span:?}");
+    assert!(span.end <= on.len(), "{span:?} past {}", on.len());
+}
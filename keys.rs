@@ -0,0 +1,120 @@
+use alloc::vec::Vec;
+
+use crate::{JSONKey, Span};
+
+/// Storage for the path of keys to the current value, along with where each key is. See
+/// [`crate::JSONIterator::with_key_chain`]
+pub trait KeyChain<'a> {
+    /// Returns `false` if there is no room for another key
+    fn push(&mut self, key: JSONKey<'a>, span: Span) -> bool;
+
+    fn pop(&mut self);
+
+    fn keys(&self) -> &[JSONKey<'a>];
+
+    /// Parallel to [`KeyChain::keys`]
+    fn spans(&self) -> &[Span];
+
+    fn last_mut(&mut self) -> Option<(&mut JSONKey<'a>, &mut Span)>;
+
+    /// The parser checks the chain through this, [`KeyChain::is_empty`] and [`KeyChain::last`]
+    /// between events, so they can be answered without [`KeyChain::keys`]
+    fn len(&self) -> usize {
+        self.keys().len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn last(&self) -> Option<JSONKey<'a>> {
+        self.keys().last().cloned()
+    }
+}
+
+/// Growable [`KeyChain`], the default
+#[derive(Debug, Default)]
+pub struct KeyVec<'a> {
+    pub(crate) keys: Vec<JSONKey<'a>>,
+    pub(crate) spans: Vec<Span>,
+}
+
+impl<'a> KeyChain<'a> for KeyVec<'a> {
+    fn push(&mut self, key: JSONKey<'a>, span: Span) -> bool {
+        self.keys.push(key);
+        self.spans.push(span);
+        true
+    }
+
+    fn pop(&mut self) {
+        self.keys.pop();
+        self.spans.pop();
+    }
+
+    fn keys(&self) -> &[JSONKey<'a>] {
+        &self.keys
+    }
+
+    fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    fn last_mut(&mut self) -> Option<(&mut JSONKey<'a>, &mut Span)> {
+        self.keys.last_mut().zip(self.spans.last_mut())
+    }
+}
+
+/// [`KeyChain`] with room for `N` keys, which does not allocate. Nesting deeper than `N` is a
+/// [`crate::JSONParseErrorReason::KeyChainFull`] error
+#[derive(Debug)]
+pub struct KeyArray<'a, const N: usize> {
+    keys: [JSONKey<'a>; N],
+    spans: [Span; N],
+    length: usize,
+}
+
+impl<const N: usize> KeyArray<'_, N> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            keys: [const { JSONKey::Index(0) }; N],
+            spans: [Span { start: 0, end: 0 }; N],
+            length: 0,
+        }
+    }
+}
+
+impl<const N: usize> Default for KeyArray<'_, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, const N: usize> KeyChain<'a> for KeyArray<'a, N> {
+    fn push(&mut self, key: JSONKey<'a>, span: Span) -> bool {
+        if self.length == N {
+            return false;
+        }
+        self.keys[self.length] = key;
+        self.spans[self.length] = span;
+        self.length += 1;
+        true
+    }
+
+    fn pop(&mut self) {
+        self.length = self.length.saturating_sub(1);
+    }
+
+    fn keys(&self) -> &[JSONKey<'a>] {
+        &self.keys[..self.length]
+    }
+
+    fn spans(&self) -> &[Span] {
+        &self.spans[..self.length]
+    }
+
+    fn last_mut(&mut self) -> Option<(&mut JSONKey<'a>, &mut Span)> {
+        let last = self.length.checked_sub(1)?;
+        Some((&mut self.keys[last], &mut self.spans[last]))
+    }
+}
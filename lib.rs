@@ -1,9 +1,69 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{borrow::Cow, vec::Vec};
+
+#[cfg(feature = "async")]
+mod asynchronous;
+mod cst;
+#[cfg(feature = "serde")]
+mod deserialize;
+mod diagnostic;
+mod escape;
+mod keys;
+mod number;
+#[cfg(feature = "std")]
+mod reader;
+mod simd;
+mod stream;
+mod tape;
+mod value;
+mod writer;
+
+use escape::Escape;
+use number::NumberPart;
+use simd::{Block, StructuralIndex};
+
+#[cfg(feature = "async")]
+pub use asynchronous::{
+    parse_async_reader, parse_async_reader_with_options, parse_stream, parse_stream_with_options,
+    try_parse_stream, try_parse_stream_with_options,
+};
+pub use cst::{SyntaxNode, SyntaxToken, SyntaxTree, TokenKind};
+#[cfg(feature = "serde")]
+pub use deserialize::{from_str, from_str_with_options, Deserializer, JSONDeserializeError};
+pub use diagnostic::{line_column, Diagnostic, LineColumn};
+pub use escape::unescape;
+pub use keys::{KeyArray, KeyChain, KeyVec};
+pub use number::JSONDecimal;
+#[cfg(feature = "std")]
+pub use reader::{parse_reader, parse_reader_with_options, JSONReadError};
+pub use stream::JSONStreamParser;
+pub use tape::{Tape, TapeNode, TapeRef};
+pub use value::{Object, Value};
+#[cfg(feature = "std")]
+pub use writer::IoWriter;
+pub use writer::{format, format_with_options, minify, JSONWriteError, JSONWriter, WriteOptions};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum JSONKey<'a> {
     Slice(&'a str),
     Index(usize),
 }
 
+impl<'a> JSONKey<'a> {
+    /// The key with escape sequences decoded. `None` for array indexes
+    #[must_use]
+    pub fn as_str(&self) -> Option<Cow<'a, str>> {
+        match self {
+            JSONKey::Slice(s) => Some(unescape(s)),
+            JSONKey::Index(_) => None,
+        }
+    }
+}
+
+/// String values and keys are the raw contents between the quotes, use [`unescape`] to decode them
 #[derive(Debug, PartialEq, Eq)]
 pub enum RootJSONValue<'a> {
     String(&'a str),
@@ -13,7 +73,98 @@ pub enum RootJSONValue<'a> {
     Null,
 }
 
-#[derive(Debug)]
+impl<'a> RootJSONValue<'a> {
+    /// String values with escape sequences decoded
+    #[must_use]
+    pub fn as_str(&self) -> Option<Cow<'a, str>> {
+        match self {
+            RootJSONValue::String(s) => Some(unescape(s)),
+            _ => None,
+        }
+    }
+
+    /// Integer numbers that fit in a [`i64`]. `None` for numbers with a fractional part or exponent
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_integer()?.try_into().ok()
+    }
+
+    /// Non-negative integer numbers that fit in a [`u64`]. `None` for numbers with a fractional part
+    /// or exponent
+    #[must_use]
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_integer()?.try_into().ok()
+    }
+
+    /// Any number, rounded to the closest [`f64`]
+    #[must_use]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            RootJSONValue::Number(n) => number::parse_float(n),
+            _ => None,
+        }
+    }
+
+    /// The number split into its digits and exponent, without any loss of precision. `None` for
+    /// JSON5 hexadecimal, `Infinity` and `NaN` numbers
+    #[must_use]
+    pub fn as_decimal(&self) -> Option<JSONDecimal<'a>> {
+        match self {
+            RootJSONValue::Number(n) if number::is_decimal(n) => Some(JSONDecimal::new(n)),
+            _ => None,
+        }
+    }
+
+    fn as_integer(&self) -> Option<i128> {
+        match self {
+            RootJSONValue::Number(n) => number::parse_integer(n),
+            _ => None,
+        }
+    }
+}
+
+/// Byte offsets into the input. `end` is exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    /// `// ...`
+    Line,
+    /// `/* ... */`
+    Block,
+    /// `# ...`
+    Hash,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct JSONComment<'a> {
+    /// Without the delimiters (`//`, `/*` and `*/` or `#`)
+    pub text: &'a str,
+    pub kind: CommentKind,
+    /// Including the delimiters. Line comments do not include the ending new line
+    pub span: Span,
+}
+
+/// Leaf values along with the start and end of objects and arrays. Start and end events are
+/// reported with the path to the object or array
+#[derive(Debug, PartialEq, Eq)]
+pub enum JSONEvent<'a> {
+    ObjectStart,
+    ObjectEnd,
+    ArrayStart,
+    ArrayEnd,
+    Value(RootJSONValue<'a>),
+    /// Only with [`ParseOptions::comment_events`]. Reported with the path at the position of the
+    /// comment. So between the members of an object it is the path to the object, after a key or
+    /// value it includes the key and in arrays it includes the index of the next or previous item
+    Comment(JSONComment<'a>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JSONParseErrorReason {
     ExpectedColon,
     ExpectedEndOfValue,
@@ -24,25 +175,144 @@ pub enum JSONParseErrorReason {
     ExpectedEndOfMultilineComment,
     /// Both for string values and keys
     ExpectedQuote,
+    /// Unknown escape character, malformed `\uXXXX` or unpaired surrogate in a string value or key
+    InvalidEscape,
+    /// Number that does not match the JSON number grammar, for example `01`, `1.` or `-`
+    InvalidNumber,
+    /// A `,` directly before a closing `}` or `]` when [`ParseOptions::trailing_commas`] is off
+    TrailingComma,
+    /// Unescaped characters below `U+0020` in strict mode
+    ControlCharacterInString,
+    /// Nesting deeper than the room in a fixed size [`KeyChain`] such as [`KeyArray`]
+    KeyChainFull,
+    /// More nested objects and arrays than [`ParseOptions::max_depth`]
+    MaxDepthExceeded,
+    /// String value or key longer than [`ParseOptions::max_string_length`]
+    MaxStringLengthExceeded,
+    /// Number longer than [`ParseOptions::max_number_length`]
+    MaxNumberLengthExceeded,
+    /// More values than [`ParseOptions::max_values`]
+    MaxValuesExceeded,
+    /// Input bytes that are not valid UTF-8
+    InvalidUTF8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct JSONParseError {
     pub at: usize,
     pub reason: JSONParseErrorReason,
 }
 
-impl std::error::Error for JSONParseError {}
+impl core::error::Error for JSONParseError {}
 
-impl std::fmt::Display for JSONParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+impl core::fmt::Display for JSONParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         f.write_fmt(format_args!(
-            "JSONParseError: {:?} at {:?}",
+            "JSONParseError: {} at {:?}",
             self.reason, self.at
         ))
     }
 }
 
+/// Which extensions to standard (RFC 8259) JSON are accepted. The [`Default`] is lenient, accepting
+/// comments and trailing commas, for reading configuration files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ParseOptions {
+    /// `// line` and `/* block */` comments
+    pub comments: bool,
+    /// `# line` comments
+    pub hash_comments: bool,
+    /// A `,` before the closing `}` or `]`
+    pub trailing_commas: bool,
+    /// Rejects unexpected characters in objects, control characters in strings, non JSON whitespace
+    /// and empty input rather than skipping over them
+    pub strict: bool,
+    /// JSON5 identifier keys, single quoted strings, additional escapes, hexadecimal numbers,
+    /// leading `+` and leading or trailing decimal points, `Infinity` and `NaN`
+    pub json5: bool,
+    /// Report comments as [`JSONEvent::Comment`]s
+    pub comment_events: bool,
+    /// Rather than stopping at the first error, continue from the next `,`, `}` or `]`. Errors are
+    /// returned from [`JSONIterator::next_event`] in between events. Exceeding a limit is not
+    /// recovered from
+    pub recover: bool,
+    /// Of objects and arrays within each other
+    pub max_depth: Option<usize>,
+    /// In bytes, of string values and keys before unescaping
+    pub max_string_length: Option<usize>,
+    /// In bytes
+    pub max_number_length: Option<usize>,
+    /// Including objects and arrays
+    pub max_values: Option<usize>,
+    /// With the `simd` feature, find the quotes, backslashes, whitespace and structural characters
+    /// 64 bytes at a time using SSE2, AVX2 or NEON instructions (depending on what the CPU supports)
+    /// and skip over whitespace, the insides of strings and input being recovered from
+    pub simd: bool,
+}
+
+impl ParseOptions {
+    /// Only accepts RFC 8259 JSON. Of the choices it leaves to implementations: numbers are not
+    /// range checked, `\u` escapes of unpaired surrogates, input that is not UTF-8 and a leading
+    /// byte order mark are rejected and there is no limit on nesting (see
+    /// `tests/json_test_suite.rs`)
+    #[must_use]
+    pub const fn strict() -> Self {
+        Self {
+            comments: false,
+            hash_comments: false,
+            trailing_commas: false,
+            strict: true,
+            json5: false,
+            comment_events: false,
+            recover: false,
+            max_depth: None,
+            max_string_length: None,
+            max_number_length: None,
+            max_values: None,
+            simd: true,
+        }
+    }
+
+    /// [JSON5](https://spec.json5.org/)
+    #[must_use]
+    pub const fn json5() -> Self {
+        Self {
+            comments: true,
+            hash_comments: false,
+            trailing_commas: true,
+            strict: false,
+            json5: true,
+            comment_events: false,
+            recover: false,
+            max_depth: None,
+            max_string_length: None,
+            max_number_length: None,
+            max_values: None,
+            simd: true,
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            comments: true,
+            hash_comments: true,
+            trailing_commas: true,
+            strict: false,
+            json5: false,
+            comment_events: false,
+            recover: false,
+            max_depth: None,
+            max_string_length: None,
+            max_number_length: None,
+            max_values: None,
+            simd: true,
+        }
+    }
+}
+
 /// If you want to return early (not parse the whole input) use [`parse_with_exit_signal`]
 ///
 /// # Errors
@@ -57,283 +327,1091 @@ pub fn parse<'a>(
     })
 }
 
+/// [`parse`] with a choice of which extensions to accept
+///
+/// # Errors
+/// Returns an error if it tries to parse input invalid under `options`
+pub fn parse_with_options<'a>(
+    on: &'a str,
+    options: ParseOptions,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>),
+) -> Result<(), JSONParseError> {
+    let mut iterator = JSONIterator::with_options(on, options);
+    while let Some(value) = iterator.next_value() {
+        cb(iterator.key_chain(), value?);
+    }
+    Ok(())
+}
+
+/// [`parse_with_options`] for input that may not be UTF-8 (see [`JSONIterator::from_bytes`])
+///
+/// # Errors
+/// Returns an error if it tries to parse input invalid under `options`, including
+/// [`JSONParseErrorReason::InvalidUTF8`] in strings and keys
+pub fn parse_bytes<'a>(
+    on: &'a [u8],
+    options: ParseOptions,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>),
+) -> Result<(), JSONParseError> {
+    let mut iterator = JSONIterator::from_bytes(on, options);
+    while let Some(value) = iterator.next_value() {
+        cb(iterator.key_chain(), value?);
+    }
+    Ok(())
+}
+
+/// A value from [`parse_bytes_raw`]
+#[derive(Debug, PartialEq, Eq)]
+pub enum RawJSONValue<'a> {
+    /// The contents between the quotes, which are not checked to be UTF-8. Use
+    /// [`core::str::from_utf8`] and then [`unescape`] to decode them
+    String(&'a [u8]),
+    /// Any other value. Never [`RootJSONValue::String`]
+    Other(RootJSONValue<'a>),
+}
+
+/// [`parse_bytes`] without checking string values are UTF-8, for input where they are only
+/// compared or copied. Keys are still checked
+///
+/// # Errors
+/// Returns an error if it tries to parse input invalid under `options`, including
+/// [`JSONParseErrorReason::InvalidUTF8`] in keys
+pub fn parse_bytes_raw<'a>(
+    on: &'a [u8],
+    options: ParseOptions,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RawJSONValue<'a>),
+) -> Result<(), JSONParseError> {
+    let mut iterator = JSONIterator::from_bytes(on, options);
+    iterator.raw_strings = true;
+    while let Some(value) = iterator.next_value() {
+        let value = match value? {
+            RootJSONValue::String(_) => {
+                // Without the quotes, which are ASCII
+                let Span { start, end } = iterator.span();
+                RawJSONValue::String(&on[start + 1..end - 1])
+            }
+            value => RawJSONValue::Other(value),
+        };
+        cb(iterator.key_chain(), value);
+    }
+    Ok(())
+}
+
+/// Parses all of `on`, continuing past errors (see [`ParseOptions::recover`]). Returns every error
+/// found. `cb` is called for values outside of the parts skipped over
+pub fn parse_with_recovery<'a>(
+    on: &'a str,
+    options: ParseOptions,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>),
+) -> Vec<JSONParseError> {
+    let options = ParseOptions {
+        recover: true,
+        ..options
+    };
+    let mut iterator = JSONIterator::with_options(on, options);
+    let mut errors = Vec::new();
+    while let Some(value) = iterator.next_value() {
+        match value {
+            Ok(value) => cb(iterator.key_chain(), value),
+            Err(error) => errors.push(error),
+        }
+    }
+    errors
+}
+
+/// [`parse`] which also reports where each value is. `key_spans` is [`JSONIterator::key_spans`]
+/// and `span` is [`JSONIterator::span`]
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_with_spans<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], &'b [Span], RootJSONValue<'a>, Span),
+) -> Result<(), JSONParseError> {
+    let mut iterator = JSONIterator::new(on);
+    while let Some(value) = iterator.next_value() {
+        cb(
+            iterator.key_chain(),
+            iterator.key_spans(),
+            value?,
+            iterator.span(),
+        );
+    }
+    Ok(())
+}
+
 enum State {
+    /// `quote` is `'` for JSON5 single quoted keys
     InKey {
-        escaped: bool,
+        escape: Escape,
         start: usize,
+        quote: char,
     },
-    Colon,
-    InObject,
-    Comment {
+    /// JSON5 unquoted keys. `escape` is the number of characters after a `\` and the value of
+    /// the `\uXXXX` digits so far
+    IdentifierKey {
         start: usize,
-        multiline: bool,
-        last_was_asterisk: bool,
-        hash: bool,
+        escape: Option<(u8, u32)>,
+    },
+    Colon,
+    InObject {
+        after_comma: bool,
+    },
+    ExpectingValue {
+        after_comma: bool,
     },
-    ExpectingValue,
     StringValue {
         start: usize,
-        escaped: bool,
+        escape: Escape,
+        quote: char,
     },
     NumberValue {
         start: usize,
+        part: NumberPart,
     },
-    TrueFalseNull {
+    /// `true`, `false`, `null` and JSON5 `Infinity` and `NaN`
+    Literal {
         start: usize,
     },
     EndOfValue,
+    /// After an error with [`ParseOptions::recover`]. Skips to a `,`, `}` or `]` in the current
+    /// object or array, stepping over strings and nested objects and arrays
+    Recover {
+        depth: usize,
+        string: Option<char>,
+        escaped: bool,
+    },
 }
 
-// TODO always pops from key_chain **unless** we are in an array.
-// TODO there are complications using this in an iterator when we yielding numbers
-fn end_of_value(
-    idx: usize,
-    chr: char,
-    state: &mut State,
-    key_chain: &mut Vec<JSONKey<'_>>,
-) -> Result<(), JSONParseError> {
-    if chr == ',' {
-        if let Some(JSONKey::Index(i)) = key_chain.last_mut() {
-            *i += 1;
-            *state = State::ExpectingValue;
+const LITERALS: &[&str] = &["true", "false", "null"];
+const JSON5_LITERALS: &[&str] = &["true", "false", "null", "Infinity", "NaN"];
+
+/// Comments can appear between any tokens, so are tracked separately from [`State`]
+struct Comment {
+    start: usize,
+    multiline: bool,
+    last_was_asterisk: bool,
+    hash: bool,
+}
+
+impl Comment {
+    /// Returns whether `chr` ends the comment
+    fn next(&mut self, idx: usize, chr: char) -> bool {
+        if chr == '\n' && !self.multiline {
+            true
+        } else if chr == '*' && self.start + 1 == idx && !self.hash {
+            self.multiline = true;
+            false
+        } else if self.multiline {
+            let end = self.last_was_asterisk && chr == '/';
+            self.last_was_asterisk = chr == '*';
+            end
         } else {
-            key_chain.pop();
-            *state = State::InObject;
+            false
         }
-    } else if let ('}', Some(JSONKey::Slice(..))) = (chr, key_chain.last()) {
-        key_chain.pop();
-    } else if let (']', Some(JSONKey::Index(..))) = (chr, key_chain.last()) {
-        key_chain.pop();
-    } else if let c @ ('/' | '#') = chr {
-        key_chain.pop();
-        *state = State::Comment {
-            last_was_asterisk: false,
-            start: idx,
-            multiline: false,
-            hash: c == '#',
+    }
+
+    /// `source` is the whole comment, including delimiters
+    fn finish<'a>(&self, source: &'a str) -> JSONComment<'a> {
+        let (kind, text) = if self.hash {
+            (CommentKind::Hash, &source[1..])
+        } else if self.multiline {
+            (CommentKind::Block, &source[2..source.len() - 2])
+        } else {
+            // A single `/` is also treated as the start of a line comment
+            let text = &source[1..];
+            (CommentKind::Line, text.strip_prefix('/').unwrap_or(text))
         };
-    } else if !chr.is_whitespace() {
-        return Err(JSONParseError {
-            at: idx,
-            reason: JSONParseErrorReason::ExpectedEndOfValue,
-        });
+        JSONComment {
+            text,
+            kind,
+            span: Span {
+                start: self.start,
+                end: self.start + source.len(),
+            },
+        }
     }
-    Ok(())
 }
 
-/// # Errors
-/// Returns an error if it tries to parse invalid JSON input
-#[allow(clippy::too_many_lines)]
-pub fn parse_with_exit_signal<'a>(
-    on: &'a str,
-    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>) -> bool,
-) -> Result<(), JSONParseError> {
-    let chars = on.char_indices();
+/// Pull based parser. Yields leaf values along with the path of keys to them
+///
+/// Iterating allocates a [`Vec`] for the path of each item. [`JSONIterator::next_value`] paired with
+/// [`JSONIterator::key_chain`] avoids this. The path itself is stored in `K`, which with
+/// [`KeyArray`] does not allocate
+#[allow(clippy::struct_excessive_bools)]
+pub struct JSONIterator<'a, K = KeyVec<'a>> {
+    on: &'a [u8],
+    /// `on` if it is known to be UTF-8
+    text: Option<&'a str>,
+    options: ParseOptions,
+    index: StructuralIndex,
+    idx: usize,
+    state: State,
+    comment: Option<Comment>,
+    keys: K,
+    span: Span,
+    /// Set after yielding [`JSONEvent::ArrayStart`], so the event is reported with the path of the array
+    push_index: bool,
+    /// Counted against [`ParseOptions::max_values`]
+    values: usize,
+    finished: bool,
+    /// More input follows `on`, so reaching its end waits rather than ending the document. Set by
+    /// [`JSONStreamParser`]
+    partial: bool,
+    /// String values are not checked to be UTF-8 and are reported as an empty
+    /// [`RootJSONValue::String`]. Set by [`parse_bytes_raw`], which reads them from the span
+    raw_strings: bool,
+}
 
-    let mut key_chain = Vec::new();
-    let mut state = State::ExpectingValue;
+impl<'a> JSONIterator<'a> {
+    #[must_use]
+    pub fn new(on: &'a str) -> Self {
+        Self::with_options(on, ParseOptions::default())
+    }
 
-    for (idx, chr) in chars {
-        match state {
-            State::InKey {
-                start,
-                ref mut escaped,
-            } => {
-                if !*escaped && chr == '"' {
-                    key_chain.push(JSONKey::Slice(&on[start..idx]));
-                    state = State::Colon;
-                } else {
-                    *escaped = chr == '\\';
+    #[must_use]
+    pub fn with_options(on: &'a str, options: ParseOptions) -> Self {
+        Self::with_key_chain(on, options, KeyVec::default())
+    }
+
+    /// Parses bytes, checking only string values, keys and reported comments are UTF-8. Other
+    /// non-ASCII input is only valid as whitespace
+    #[must_use]
+    pub fn from_bytes(on: &'a [u8], options: ParseOptions) -> Self {
+        Self::from_parts(on, None, options, KeyVec::default())
+    }
+}
+
+impl<'a, K: KeyChain<'a>> JSONIterator<'a, K> {
+    /// Stores the path in `keys`. For example `KeyArray::<16>::new()` parses without allocating,
+    /// up to 16 objects and arrays deep
+    #[must_use]
+    pub fn with_key_chain(on: &'a str, options: ParseOptions, keys: K) -> Self {
+        Self::from_parts(on.as_bytes(), Some(on), options, keys)
+    }
+
+    fn from_parts(on: &'a [u8], text: Option<&'a str>, options: ParseOptions, keys: K) -> Self {
+        Self {
+            on,
+            text,
+            options,
+            index: StructuralIndex::new(options.simd),
+            idx: 0,
+            state: State::ExpectingValue { after_comma: false },
+            comment: None,
+            keys,
+            span: Span { start: 0, end: 0 },
+            push_index: false,
+            values: 0,
+            finished: false,
+            partial: false,
+            raw_strings: false,
+        }
+    }
+
+    /// The path to the value (or object or array) last returned from [`JSONIterator::next_value`]
+    /// or [`JSONIterator::next_event`]
+    #[must_use]
+    pub fn key_chain(&self) -> &[JSONKey<'a>] {
+        self.keys.keys()
+    }
+
+    /// The location of each of the [`JSONIterator::key_chain`] entries. For [`JSONKey::Slice`] this
+    /// is the key (including quotes). For [`JSONKey::Index`] it is the span of the item, or its opening
+    /// bracket if it is an object or array
+    #[must_use]
+    pub fn key_spans(&self) -> &[Span] {
+        self.keys.spans()
+    }
+
+    /// The location of the event last returned. For string values includes the quotes
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// For parsers building a document, once iterating has finished. Outside of strict mode input
+    /// without a value (empty or only comments) is accepted, for which this is an error
+    pub(crate) fn expect_value(&self) -> Result<(), JSONParseError> {
+        if self.values == 0 {
+            Err(JSONParseError {
+                at: self.on.len(),
+                reason: JSONParseErrorReason::ExpectedValue,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Parses up to and including the next value, skipping over object and array events.
+    /// After an error (or the end of input) returns `None`
+    pub fn next_value(&mut self) -> Option<Result<RootJSONValue<'a>, JSONParseError>> {
+        loop {
+            match self.next_event()? {
+                Ok(JSONEvent::Value(value)) => return Some(Ok(value)),
+                Ok(_) => {}
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+
+    /// Parses up to and including the next event. After an error (or the end of input) returns `None`
+    #[allow(clippy::too_many_lines)]
+    pub fn next_event(&mut self) -> Option<Result<JSONEvent<'a>, JSONParseError>> {
+        if self.finished {
+            return None;
+        }
+
+        if self.push_index {
+            self.push_index = false;
+            let position = Span {
+                start: self.idx,
+                end: self.idx,
+            };
+            if let Err(error) = self.push_key(JSONKey::Index(0), position) {
+                return Some(Err(error));
+            }
+        }
+
+        let on = self.on;
+        loop {
+            if !self.index.is_scalar() {
+                if let Err(error) = self.skip_ahead() {
+                    return Some(Err(error));
                 }
             }
-            State::StringValue {
-                start,
-                ref mut escaped,
-            } => {
-                if !*escaped && chr == '"' {
-                    state = State::EndOfValue;
-                    let res = cb(&key_chain, RootJSONValue::String(&on[start..idx]));
-                    if res {
-                        return Ok(());
+            let Some(&byte) = on.get(self.idx) else {
+                break;
+            };
+            let idx = self.idx;
+            let chr = if byte.is_ascii() {
+                char::from(byte)
+            } else if self.skips_non_ascii() {
+                // Only ASCII is significant in comments and strings. Strings are checked to be
+                // UTF-8 as a whole
+                if let State::InKey { start, .. } | State::StringValue { start, .. } = self.state {
+                    if exceeds(self.options.max_string_length, idx + 1 - start) {
+                        return Some(
+                            self.limit(idx, JSONParseErrorReason::MaxStringLengthExceeded),
+                        );
                     }
-                } else {
-                    *escaped = chr == '\\';
                 }
-            }
-            State::Colon => {
-                if chr == ':' {
-                    state = State::ExpectingValue;
-                } else if !chr.is_whitespace() {
-                    return Err(JSONParseError {
-                        at: idx,
-                        reason: JSONParseErrorReason::ExpectedColon,
-                    });
+                if let Some(ref mut comment) = self.comment {
+                    comment.last_was_asterisk = false;
                 }
+                self.idx += 1;
+                continue;
+            } else if let Some(chr) = decode(&on[idx..]) {
+                chr
+            } else {
+                return Some(self.error(idx, JSONParseErrorReason::InvalidUTF8));
+            };
+
+            if let Some(mut comment) = self.comment.take() {
+                self.idx += chr.len_utf8();
+                if !comment.next(idx, chr) {
+                    self.comment = Some(comment);
+                    continue;
+                }
+                if self.options.comment_events {
+                    let end = if comment.multiline { self.idx } else { idx };
+                    let comment = match self.str(comment.start, end) {
+                        Ok(source) => comment.finish(source),
+                        Err(at) => return Some(self.error(at, JSONParseErrorReason::InvalidUTF8)),
+                    };
+                    let span = comment.span;
+                    return Some(Ok(self.yield_event(JSONEvent::Comment(comment), span)));
+                }
+                continue;
             }
-            State::EndOfValue => {
-                end_of_value(idx, chr, &mut state, &mut key_chain)?;
-            }
-            State::Comment {
-                ref mut last_was_asterisk,
-                ref mut multiline,
-                hash,
-                start,
-            } => {
-                if chr == '\n' && !*multiline {
-                    if let Some(JSONKey::Index(..)) = key_chain.last() {
-                        state = State::ExpectingValue;
-                    } else {
-                        state = State::InObject;
-                    }
-                } else if chr == '*' && start + 1 == idx && !hash {
-                    *multiline = true;
-                } else if *multiline {
-                    if *last_was_asterisk && chr == '/' {
-                        if let Some(JSONKey::Index(..)) = key_chain.last() {
-                            state = State::ExpectingValue;
-                        } else {
-                            state = State::InObject;
+
+            match self.state {
+                State::InKey {
+                    start,
+                    ref mut escape,
+                    quote,
+                } => {
+                    if escape.can_end() && chr == quote {
+                        let span = Span {
+                            start: start - quote.len_utf8(),
+                            end: idx + chr.len_utf8(),
+                        };
+                        let key = match self.str(start, idx) {
+                            Ok(key) => key,
+                            Err(at) => {
+                                return Some(self.error(at, JSONParseErrorReason::InvalidUTF8))
+                            }
+                        };
+                        if let Err(error) = self.push_key(JSONKey::Slice(key), span) {
+                            return Some(Err(error));
                         }
+                        self.state = State::Colon;
+                    } else if exceeds(self.options.max_string_length, idx + chr.len_utf8() - start)
+                    {
+                        return Some(
+                            self.limit(idx, JSONParseErrorReason::MaxStringLengthExceeded),
+                        );
+                    } else if self.options.strict && chr < ' ' {
+                        return Some(
+                            self.error(idx, JSONParseErrorReason::ControlCharacterInString),
+                        );
+                    } else if let Some(next) = escape.next(chr, self.options.json5) {
+                        *escape = next;
                     } else {
-                        *last_was_asterisk = chr == '*';
+                        return Some(self.error(idx, JSONParseErrorReason::InvalidEscape));
                     }
                 }
-            }
-            State::ExpectingValue => {
-                state = match chr {
-                    '{' => State::InObject,
-                    '[' => {
-                        key_chain.push(JSONKey::Index(0));
-                        State::ExpectingValue
-                    }
-                    '"' => State::StringValue {
-                        start: idx + '"'.len_utf8(),
-                        escaped: false,
-                    },
-                    c @ ('/' | '#') => State::Comment {
-                        last_was_asterisk: false,
-                        start: idx,
-                        multiline: false,
-                        hash: c == '#',
-                    },
-                    '0'..='9' | '-' => State::NumberValue { start: idx },
-                    't' | 'f' | 'n' => State::TrueFalseNull { start: idx },
-                    chr if chr.is_whitespace() => state,
-                    _ => {
-                        return Err(JSONParseError {
-                            at: idx,
-                            reason: JSONParseErrorReason::ExpectedValue,
-                        })
+                State::IdentifierKey {
+                    start,
+                    ref mut escape,
+                } => {
+                    if let Some((read, value)) = *escape {
+                        // Only `\uXXXX`, of a character that is valid at that point
+                        *escape = match (read, chr.to_digit(16)) {
+                            (0, _) if chr == 'u' => Some((1, 0)),
+                            (1..=3, Some(digit)) => Some((read + 1, (value << 4) | digit)),
+                            (4, Some(digit))
+                                if char::from_u32((value << 4) | digit).is_some_and(
+                                    |decoded| {
+                                        if idx + 1 - start == "\\uXXXX".len() {
+                                            is_identifier_start(decoded)
+                                        } else {
+                                            is_identifier_continue(decoded)
+                                        }
+                                    },
+                                ) =>
+                            {
+                                None
+                            }
+                            _ => return Some(self.error(idx, JSONParseErrorReason::InvalidEscape)),
+                        };
+                    } else if chr == '\\' {
+                        *escape = Some((0, 0));
+                    } else if !is_identifier_continue(chr) {
+                        // Not consumed, it should be a `:`
+                        let span = Span { start, end: idx };
+                        // Identifier characters have been decoded
+                        let key = self.ascii_or_decoded(start, idx);
+                        if let Err(error) = self.push_key(JSONKey::Slice(key), span) {
+                            return Some(Err(error));
+                        }
+                        self.state = State::Colon;
+                        continue;
                     }
                 }
-            }
-            State::InObject => {
-                if chr == '"' {
-                    state = State::InKey {
-                        escaped: false,
-                        start: idx + '"'.len_utf8(),
-                    };
-                } else if chr == '}' {
-                    if let Some(JSONKey::Index(..)) = key_chain.last() {
-                        state = State::ExpectingValue;
+                State::StringValue {
+                    start,
+                    ref mut escape,
+                    quote,
+                } => {
+                    if escape.can_end() && chr == quote {
+                        let value = if self.raw_strings {
+                            RootJSONValue::String("")
+                        } else {
+                            match self.str(start, idx) {
+                                Ok(value) => RootJSONValue::String(value),
+                                Err(at) => {
+                                    return Some(self.error(at, JSONParseErrorReason::InvalidUTF8))
+                                }
+                            }
+                        };
+                        self.state = State::EndOfValue;
+                        self.idx += chr.len_utf8();
+                        let span = Span {
+                            start: start - quote.len_utf8(),
+                            end: self.idx,
+                        };
+                        return Some(Ok(self.yield_event(JSONEvent::Value(value), span)));
+                    } else if exceeds(self.options.max_string_length, idx + chr.len_utf8() - start)
+                    {
+                        return Some(
+                            self.limit(idx, JSONParseErrorReason::MaxStringLengthExceeded),
+                        );
+                    } else if self.options.strict && chr < ' ' {
+                        return Some(
+                            self.error(idx, JSONParseErrorReason::ControlCharacterInString),
+                        );
+                    } else if let Some(next) = escape.next(chr, self.options.json5) {
+                        *escape = next;
                     } else {
-                        state = State::InObject;
+                        return Some(self.error(idx, JSONParseErrorReason::InvalidEscape));
                     }
                 }
-            }
-            State::NumberValue { start } => {
-                // TODO actual number handing
-                if chr.is_whitespace() || matches!(chr, '}' | ',' | ']') {
-                    let res = cb(&key_chain, RootJSONValue::Number(&on[start..idx]));
-                    if res {
-                        return Ok(());
+                State::Colon => {
+                    if chr == ':' {
+                        self.state = State::ExpectingValue { after_comma: false };
+                    } else if !self.skip(idx, chr) {
+                        return Some(self.error(idx, JSONParseErrorReason::ExpectedColon));
                     }
-                    state = State::EndOfValue;
-                    end_of_value(idx, chr, &mut state, &mut key_chain)?;
                 }
-            }
-            State::TrueFalseNull { start } => {
-                let diff = idx - start + 1;
-                if diff < 4 {
-                    // ...
-                } else if diff == 4 {
-                    match &on[start..=idx] {
-                        "true" => {
-                            let res = cb(&key_chain, RootJSONValue::True);
-                            if res {
-                                return Ok(());
+                State::EndOfValue => match self.end_of_value(idx, chr) {
+                    Ok(Some(event)) => {
+                        self.idx += chr.len_utf8();
+                        let span = Span {
+                            start: idx,
+                            end: self.idx,
+                        };
+                        return Some(Ok(self.yield_event(event, span)));
+                    }
+                    Ok(None) => {}
+                    Err(JSONParseError { at, reason }) => return Some(self.error(at, reason)),
+                },
+                State::ExpectingValue { after_comma } => {
+                    let state = match chr {
+                        '{' => {
+                            if let Err(error) = self.start_value(idx, true) {
+                                return Some(Err(error));
+                            }
+                            self.state = State::InObject { after_comma: false };
+                            self.idx += chr.len_utf8();
+                            let span = Span {
+                                start: idx,
+                                end: self.idx,
+                            };
+                            return Some(Ok(self.yield_event(JSONEvent::ObjectStart, span)));
+                        }
+                        '[' => {
+                            if let Err(error) = self.start_value(idx, true) {
+                                return Some(Err(error));
                             }
-                            state = State::EndOfValue;
+                            self.state = State::ExpectingValue { after_comma: false };
+                            self.push_index = true;
+                            self.idx += chr.len_utf8();
+                            let span = Span {
+                                start: idx,
+                                end: self.idx,
+                            };
+                            return Some(Ok(self.yield_event(JSONEvent::ArrayStart, span)));
                         }
-                        "null" => {
-                            let res = cb(&key_chain, RootJSONValue::Null);
-                            if res {
-                                return Ok(());
+                        ']' if matches!(self.keys.last(), Some(JSONKey::Index(..))) => {
+                            if after_comma && !self.options.trailing_commas {
+                                return Some(self.error(idx, JSONParseErrorReason::TrailingComma));
                             }
-                            state = State::EndOfValue;
+                            self.pop_key();
+                            self.state = State::EndOfValue;
+                            self.idx += chr.len_utf8();
+                            let span = Span {
+                                start: idx,
+                                end: self.idx,
+                            };
+                            return Some(Ok(self.yield_event(JSONEvent::ArrayEnd, span)));
+                        }
+                        '"' => State::StringValue {
+                            start: idx + '"'.len_utf8(),
+                            escape: Escape::None,
+                            quote: '"',
+                        },
+                        '\'' if self.options.json5 => State::StringValue {
+                            start: idx + '\''.len_utf8(),
+                            escape: Escape::None,
+                            quote: '\'',
+                        },
+                        '0'..='9' | '-' => State::NumberValue {
+                            start: idx,
+                            part: NumberPart::start(chr),
+                        },
+                        '+' | '.' if self.options.json5 => State::NumberValue {
+                            start: idx,
+                            part: NumberPart::start(chr),
+                        },
+                        't' | 'f' | 'n' => State::Literal { start: idx },
+                        'I' | 'N' if self.options.json5 => State::Literal { start: idx },
+                        chr if self.skip(idx, chr) => State::ExpectingValue { after_comma },
+                        _ => return Some(self.error(idx, JSONParseErrorReason::ExpectedValue)),
+                    };
+                    if !matches!(state, State::ExpectingValue { .. }) {
+                        if let Err(error) = self.start_value(idx, false) {
+                            return Some(Err(error));
+                        }
+                    }
+                    self.state = state;
+                }
+                State::InObject { after_comma } => {
+                    if chr == '"' || (chr == '\'' && self.options.json5) {
+                        self.state = State::InKey {
+                            escape: Escape::None,
+                            start: idx + chr.len_utf8(),
+                            quote: chr,
+                        };
+                    } else if self.options.json5 && (is_identifier_start(chr) || chr == '\\') {
+                        self.state = State::IdentifierKey {
+                            start: idx,
+                            escape: None,
+                        };
+                        // Handled as the start of the key, which may be an escape
+                        continue;
+                    } else if chr == '}' {
+                        if after_comma && !self.options.trailing_commas {
+                            return Some(self.error(idx, JSONParseErrorReason::TrailingComma));
+                        }
+                        self.state = State::EndOfValue;
+                        self.idx += chr.len_utf8();
+                        let span = Span {
+                            start: idx,
+                            end: self.idx,
+                        };
+                        return Some(Ok(self.yield_event(JSONEvent::ObjectEnd, span)));
+                    } else if !self.skip(idx, chr) && self.options.strict {
+                        return Some(self.error(idx, JSONParseErrorReason::ExpectedQuote));
+                    }
+                }
+                State::NumberValue {
+                    start,
+                    ref mut part,
+                } => {
+                    let json5 = self.options.json5;
+                    if let Some(next) = part.next(chr, json5) {
+                        if exceeds(self.options.max_number_length, idx + chr.len_utf8() - start) {
+                            return Some(
+                                self.limit(idx, JSONParseErrorReason::MaxNumberLengthExceeded),
+                            );
+                        }
+                        *part = next;
+                    } else if json5 && part.is_sign() && matches!(chr, 'I' | 'N') {
+                        // Signed `Infinity` or `NaN`
+                        self.state = State::Literal { start };
+                        continue;
+                    } else if part.is_complete(json5)
+                        && !(chr.is_alphanumeric() || matches!(chr, '.' | '+' | '-'))
+                    {
+                        // The next character is not consumed here, it is handled as the end of the
+                        // value on the next call (an error if it is not a delimiter). This leaves
+                        // `key_chain` pointing at this number
+                        self.state = State::EndOfValue;
+                        let value = RootJSONValue::Number(self.ascii_or_decoded(start, idx));
+                        let span = Span { start, end: idx };
+                        return Some(Ok(self.yield_event(JSONEvent::Value(value), span)));
+                    } else {
+                        return Some(self.error(idx, JSONParseErrorReason::InvalidNumber));
+                    }
+                }
+                State::Recover {
+                    ref mut depth,
+                    ref mut string,
+                    ref mut escaped,
+                } => {
+                    if let Some(quote) = *string {
+                        if !*escaped && chr == quote {
+                            *string = None;
                         }
-                        "fals" => {}
-                        _ => {
-                            return Err(JSONParseError {
-                                at: idx,
-                                reason: JSONParseErrorReason::ExpectedTrueFalseNull,
-                            })
+                        *escaped = !*escaped && chr == '\\';
+                    } else {
+                        match chr {
+                            '"' => *string = Some('"'),
+                            '\'' if self.options.json5 => *string = Some('\''),
+                            '{' | '[' => *depth += 1,
+                            '}' | ']' if *depth > 0 => *depth -= 1,
+                            ',' | '}' | ']' if *depth == 0 => {
+                                if let (',', Some(_))
+                                | ('}', Some(JSONKey::Slice(..)))
+                                | (']', Some(JSONKey::Index(..))) = (chr, self.keys.last())
+                                {
+                                    // Handled as the end of the value
+                                    self.state = State::EndOfValue;
+                                    continue;
+                                }
+                            }
+                            _ => {}
                         }
                     }
-                } else if let "false" = &on[start..=idx] {
-                    cb(&key_chain, RootJSONValue::False);
-                    state = State::EndOfValue;
+                }
+                State::Literal { start } => {
+                    let end = idx + chr.len_utf8();
+                    let (literals, text) = if self.options.json5 {
+                        let sign = on[start..end]
+                            .iter()
+                            .take_while(|b| matches!(b, b'+' | b'-'));
+                        (JSON5_LITERALS, &on[start + sign.count()..end])
+                    } else {
+                        (LITERALS, &on[start..end])
+                    };
+                    let Some(literal) = literals
+                        .iter()
+                        .find(|literal| literal.as_bytes().starts_with(text))
+                    else {
+                        return Some(self.error(idx, JSONParseErrorReason::ExpectedTrueFalseNull));
+                    };
+                    if literal.len() == text.len() {
+                        let value = match *literal {
+                            "true" => RootJSONValue::True,
+                            "false" => RootJSONValue::False,
+                            "null" => RootJSONValue::Null,
+                            _ => RootJSONValue::Number(self.ascii_or_decoded(start, end)),
+                        };
+                        self.state = State::EndOfValue;
+                        self.idx += chr.len_utf8();
+                        let span = Span {
+                            start,
+                            end: self.idx,
+                        };
+                        return Some(Ok(self.yield_event(JSONEvent::Value(value), span)));
+                    }
+                }
+            }
+            self.idx += chr.len_utf8();
+        }
+
+        if self.partial {
+            return None;
+        }
+        self.finished = true;
+        let at = on.len();
+        if let Some(comment) = self.comment.take() {
+            if comment.multiline {
+                return Some(self.error(at, JSONParseErrorReason::ExpectedEndOfMultilineComment));
+            } else if self.options.comment_events {
+                // Finishes on the next call
+                self.finished = false;
+                let comment = match self.str(comment.start, at) {
+                    Ok(source) => comment.finish(source),
+                    Err(at) => return Some(self.error(at, JSONParseErrorReason::InvalidUTF8)),
+                };
+                let span = comment.span;
+                return Some(Ok(self.yield_event(JSONEvent::Comment(comment), span)));
+            }
+        }
+        match self.state {
+            State::InKey { .. } | State::StringValue { .. } => {
+                Some(self.error(at, JSONParseErrorReason::ExpectedQuote))
+            }
+            State::Colon | State::IdentifierKey { .. } => {
+                Some(self.error(at, JSONParseErrorReason::ExpectedColon))
+            }
+            State::ExpectingValue { .. } if self.options.strict => {
+                Some(self.error(at, JSONParseErrorReason::ExpectedValue))
+            }
+            State::Recover {
+                string: Some(_), ..
+            } => Some(self.error(at, JSONParseErrorReason::ExpectedQuote)),
+            State::EndOfValue | State::ExpectingValue { .. } | State::Recover { .. } => {
+                if self.keys.is_empty() {
+                    None
                 } else {
-                    return Err(JSONParseError {
-                        at: idx,
-                        reason: JSONParseErrorReason::ExpectedTrueFalseNull,
-                    });
+                    Some(self.error(at, JSONParseErrorReason::ExpectedBracket))
+                }
+            }
+            State::InObject { .. } => Some(self.error(at, JSONParseErrorReason::ExpectedBracket)),
+            State::NumberValue { start, part } => {
+                if !part.is_complete(self.options.json5) {
+                    return Some(self.error(at, JSONParseErrorReason::InvalidNumber));
                 }
+                self.finished = false;
+                self.state = State::EndOfValue;
+                let value = RootJSONValue::Number(self.ascii_or_decoded(start, at));
+                Some(Ok(self.yield_event(
+                    JSONEvent::Value(value),
+                    Span { start, end: at },
+                )))
+            }
+            State::Literal { start: _ } => {
+                Some(self.error(at, JSONParseErrorReason::ExpectedTrueFalseNull))
             }
         }
     }
 
-    match state {
-        State::InKey { .. } | State::StringValue { .. } => Err(JSONParseError {
-            at: on.len(),
-            reason: JSONParseErrorReason::ExpectedQuote,
-        }),
-        State::Colon => Err(JSONParseError {
-            at: on.len(),
-            reason: JSONParseErrorReason::ExpectedColon,
-        }),
-        State::Comment { multiline, .. } => {
-            if multiline {
-                Ok(())
+    /// With [`ParseOptions::simd`], moves to the next character that the state machine needs to
+    /// look at, using the classified blocks of [`StructuralIndex`]
+    fn skip_ahead(&mut self) -> Result<(), JSONParseError> {
+        if self.comment.is_some() {
+            return Ok(());
+        }
+        let quotes = |block: &Block, quote: char| {
+            if quote == '\'' {
+                block.single_quote
             } else {
-                Err(JSONParseError {
-                    at: on.len(),
-                    reason: JSONParseErrorReason::ExpectedEndOfMultilineComment,
-                })
+                block.double_quote
+            }
+        };
+        match self.state {
+            State::InKey {
+                start,
+                escape: Escape::None,
+                quote,
+            }
+            | State::StringValue {
+                start,
+                escape: Escape::None,
+                quote,
+            } => {
+                self.idx = self.index.find(self.on, self.idx, |block| {
+                    quotes(block, quote) | block.backslash | block.control
+                });
+                if let Some(max) = self.options.max_string_length {
+                    if self.idx - start > max {
+                        return self
+                            .limit(start + max, JSONParseErrorReason::MaxStringLengthExceeded);
+                    }
+                }
+            }
+            State::Colon
+            | State::InObject { .. }
+            | State::ExpectingValue { .. }
+            | State::EndOfValue => {
+                self.idx = self
+                    .index
+                    .find(self.on, self.idx, |block| !block.whitespace);
+            }
+            State::Recover {
+                string: Some(quote),
+                escaped: false,
+                ..
+            } => {
+                self.idx = self.index.find(self.on, self.idx, |block| {
+                    quotes(block, quote) | block.backslash
+                });
+            }
+            State::Recover { string: None, .. } => {
+                // Only brackets, commas and the start of strings are looked at
+                let json5 = self.options.json5;
+                self.idx = self.index.find(self.on, self.idx, |block| {
+                    block.structural
+                        | block.double_quote
+                        | if json5 { block.single_quote } else { 0 }
+                });
             }
+            _ => {}
         }
-        State::EndOfValue | State::ExpectingValue => {
-            if key_chain.is_empty() {
-                Ok(())
-            } else {
-                Err(JSONParseError {
-                    at: on.len(),
-                    reason: JSONParseErrorReason::ExpectedBracket,
-                })
+        Ok(())
+    }
+
+    /// The input between `start` and `end`, or where it is not UTF-8
+    fn str(&self, start: usize, end: usize) -> Result<&'a str, usize> {
+        if let Some(text) = self.text {
+            Ok(&text[start..end])
+        } else {
+            core::str::from_utf8(&self.on[start..end]).map_err(|error| start + error.valid_up_to())
+        }
+    }
+
+    /// [`JSONIterator::str`] for numbers, literals and identifiers, which are ASCII or have been
+    /// decoded character by character
+    fn ascii_or_decoded(&self, start: usize, end: usize) -> &'a str {
+        self.str(start, end).unwrap_or_default()
+    }
+
+    /// Non-ASCII characters are not decoded in comments, strings (outside of escapes) and while
+    /// recovering
+    fn skips_non_ascii(&self) -> bool {
+        self.comment.is_some()
+            || matches!(
+                self.state,
+                State::InKey {
+                    escape: Escape::None,
+                    ..
+                } | State::StringValue {
+                    escape: Escape::None,
+                    ..
+                } | State::Recover { .. }
+            )
+    }
+
+    fn yield_event(&mut self, event: JSONEvent<'a>, span: Span) -> JSONEvent<'a> {
+        self.span = span;
+        if let (
+            JSONEvent::Value(_) | JSONEvent::ObjectStart | JSONEvent::ArrayStart,
+            Some(JSONKey::Index(_)),
+        ) = (&event, self.keys.last())
+        {
+            if let Some((_, last)) = self.keys.last_mut() {
+                *last = span;
             }
         }
-        State::InObject => Err(JSONParseError {
-            at: on.len(),
-            reason: JSONParseErrorReason::ExpectedBracket,
-        }),
-        State::NumberValue { start } => {
-            // TODO actual number handing
-            let _result = cb(&key_chain, RootJSONValue::Number(&on[start..]));
+        event
+    }
+
+    fn push_key(&mut self, key: JSONKey<'a>, span: Span) -> Result<(), JSONParseError> {
+        if self.keys.push(key, span) {
             Ok(())
+        } else {
+            self.limit(span.start, JSONParseErrorReason::KeyChainFull)
         }
-        State::TrueFalseNull { start: _ } => Err(JSONParseError {
-            at: on.len(),
-            reason: JSONParseErrorReason::ExpectedTrueFalseNull,
-        }),
     }
+
+    /// Checks [`ParseOptions::max_values`] and for objects and arrays [`ParseOptions::max_depth`]
+    fn start_value(&mut self, at: usize, nested: bool) -> Result<(), JSONParseError> {
+        self.values += 1;
+        if exceeds(self.options.max_values, self.values) {
+            self.limit(at, JSONParseErrorReason::MaxValuesExceeded)
+        } else if nested && exceeds(self.options.max_depth, self.keys.len() + 1) {
+            self.limit(at, JSONParseErrorReason::MaxDepthExceeded)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Exceeding a limit (including running out of room in the [`KeyChain`]) ends parsing, even
+    /// with [`ParseOptions::recover`]
+    fn limit<T>(&mut self, at: usize, reason: JSONParseErrorReason) -> Result<T, JSONParseError> {
+        self.finished = true;
+        Err(JSONParseError { at, reason })
+    }
+
+    fn pop_key(&mut self) {
+        self.keys.pop();
+    }
+
+    fn error<T>(&mut self, at: usize, reason: JSONParseErrorReason) -> Result<T, JSONParseError> {
+        if self.options.recover && at < self.on.len() {
+            self.recover();
+        } else {
+            self.finished = true;
+        }
+        Err(JSONParseError { at, reason })
+    }
+
+    /// Sets up [`State::Recover`] after an error at `self.idx`
+    fn recover(&mut self) {
+        let string = match self.state {
+            State::InKey { quote, .. } | State::StringValue { quote, .. } => {
+                // Skip past the errored character, so it is not taken as the end of the string
+                let chr = self.on.get(self.idx..).and_then(decode);
+                self.idx += chr.map_or(1, char::len_utf8).min(self.on.len() - self.idx);
+                if chr == Some(quote) {
+                    None
+                } else {
+                    Some(quote)
+                }
+            }
+            _ => None,
+        };
+        if let State::InObject { .. } | State::InKey { .. } | State::IdentifierKey { .. } =
+            self.state
+        {
+            // Stands in for the key, so that resynchronising at a `,` or `}` applies to this object
+            let span = Span {
+                start: self.idx,
+                end: self.idx,
+            };
+            if self.push_key(JSONKey::Slice(""), span).is_err() {
+                return;
+            }
+        }
+        self.comment = None;
+        self.state = State::Recover {
+            depth: 0,
+            string,
+            escaped: false,
+        };
+    }
+
+    /// Whitespace and the start of comments. Returns whether `chr` was skipped
+    fn skip(&mut self, idx: usize, chr: char) -> bool {
+        let comment = match chr {
+            '/' => self.options.comments,
+            '#' => self.options.hash_comments,
+            _ => false,
+        };
+        if comment {
+            self.comment = Some(Comment {
+                start: idx,
+                multiline: false,
+                last_was_asterisk: false,
+                hash: chr == '#',
+            });
+            true
+        } else if self.options.strict {
+            matches!(chr, ' ' | '\t' | '\n' | '\r')
+        } else {
+            chr.is_whitespace() || (self.options.json5 && chr == '\u{feff}')
+        }
+    }
+
+    fn end_of_value(
+        &mut self,
+        idx: usize,
+        chr: char,
+    ) -> Result<Option<JSONEvent<'a>>, JSONParseError> {
+        match (chr, self.keys.last_mut()) {
+            (',', Some((JSONKey::Index(i), _))) => {
+                *i += 1;
+                self.state = State::ExpectingValue { after_comma: true };
+            }
+            (',', Some((JSONKey::Slice(..), _))) => {
+                self.pop_key();
+                self.state = State::InObject { after_comma: true };
+            }
+            ('}', Some((JSONKey::Slice(..), _))) => {
+                self.pop_key();
+                return Ok(Some(JSONEvent::ObjectEnd));
+            }
+            (']', Some((JSONKey::Index(..), _))) => {
+                self.pop_key();
+                return Ok(Some(JSONEvent::ArrayEnd));
+            }
+            _ => {
+                if !self.skip(idx, chr) {
+                    return Err(JSONParseError {
+                        at: idx,
+                        reason: JSONParseErrorReason::ExpectedEndOfValue,
+                    });
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// The character at the start of `on`, if it is valid UTF-8
+fn decode(on: &[u8]) -> Option<char> {
+    let length = match on.first()? {
+        0..=0x7f => 1,
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => return None,
+    };
+    core::str::from_utf8(on.get(..length)?).ok()?.chars().next()
+}
+
+fn exceeds(limit: Option<usize>, length: usize) -> bool {
+    limit.is_some_and(|limit| length > limit)
+}
+
+fn is_identifier_start(chr: char) -> bool {
+    chr == '$' || chr == '_' || chr.is_alphabetic()
+}
+
+fn is_identifier_continue(chr: char) -> bool {
+    is_identifier_start(chr) || chr.is_alphanumeric() || matches!(chr, '\u{200c}' | '\u{200d}')
+}
+
+impl<'a, K: KeyChain<'a>> Iterator for JSONIterator<'a, K> {
+    type Item = Result<(Vec<JSONKey<'a>>, RootJSONValue<'a>), JSONParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_value()
+            .map(|value| value.map(|value| (self.key_chain().to_vec(), value)))
+    }
+}
+
+impl<'a, K: KeyChain<'a>> core::iter::FusedIterator for JSONIterator<'a, K> {}
+
+/// Like [`parse`] but also reports the start and end of objects and arrays
+///
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_events<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], JSONEvent<'a>),
+) -> Result<(), JSONParseError> {
+    let mut iterator = JSONIterator::new(on);
+    while let Some(event) = iterator.next_event() {
+        cb(iterator.key_chain(), event?);
+    }
+    Ok(())
+}
+
+/// # Errors
+/// Returns an error if it tries to parse invalid JSON input
+pub fn parse_with_exit_signal<'a>(
+    on: &'a str,
+    mut cb: impl for<'b> FnMut(&'b [JSONKey<'a>], RootJSONValue<'a>) -> bool,
+) -> Result<(), JSONParseError> {
+    let mut iterator = JSONIterator::new(on);
+    while let Some(value) = iterator.next_value() {
+        if cb(iterator.key_chain(), value?) {
+            break;
+        }
+    }
+    Ok(())
 }
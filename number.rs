@@ -0,0 +1,227 @@
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
+
+/// Position in the number grammar `-? (0 | [1-9] [0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`.
+///
+/// JSON5 additionally allows a leading `+`, leading and trailing decimal points and hexadecimal
+/// integers. `Infinity` and `NaN` are handled as literals
+#[derive(Clone, Copy)]
+pub(crate) enum NumberPart {
+    Sign,
+    Zero,
+    Integer,
+    LeadingPoint,
+    Point,
+    Fraction,
+    Exponent,
+    ExponentSign,
+    ExponentDigits,
+    HexStart,
+    Hex,
+}
+
+impl NumberPart {
+    /// For the first character of a number, one of `-` or `0..=9` (or JSON5 `+` and `.`)
+    pub(crate) fn start(chr: char) -> Self {
+        match chr {
+            '-' | '+' => NumberPart::Sign,
+            '0' => NumberPart::Zero,
+            '.' => NumberPart::LeadingPoint,
+            _ => NumberPart::Integer,
+        }
+    }
+
+    /// Returns `None` if `chr` does not continue the number
+    pub(crate) fn next(self, chr: char, json5: bool) -> Option<Self> {
+        match (self, chr) {
+            (NumberPart::Sign, '0') => Some(NumberPart::Zero),
+            (NumberPart::Sign, '1'..='9') | (NumberPart::Integer, '0'..='9') => {
+                Some(NumberPart::Integer)
+            }
+            (NumberPart::Sign, '.') if json5 => Some(NumberPart::LeadingPoint),
+            (NumberPart::Zero | NumberPart::Integer, '.') => Some(NumberPart::Point),
+            (NumberPart::LeadingPoint | NumberPart::Point | NumberPart::Fraction, '0'..='9') => {
+                Some(NumberPart::Fraction)
+            }
+            (NumberPart::Zero | NumberPart::Integer | NumberPart::Fraction, 'e' | 'E') => {
+                Some(NumberPart::Exponent)
+            }
+            (NumberPart::Point, 'e' | 'E') if json5 => Some(NumberPart::Exponent),
+            (NumberPart::Exponent, '+' | '-') => Some(NumberPart::ExponentSign),
+            (
+                NumberPart::Exponent | NumberPart::ExponentSign | NumberPart::ExponentDigits,
+                '0'..='9',
+            ) => Some(NumberPart::ExponentDigits),
+            (NumberPart::Zero, 'x' | 'X') if json5 => Some(NumberPart::HexStart),
+            (NumberPart::HexStart | NumberPart::Hex, chr) if chr.is_ascii_hexdigit() => {
+                Some(NumberPart::Hex)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the number can end here
+    pub(crate) fn is_complete(self, json5: bool) -> bool {
+        match self {
+            NumberPart::Zero
+            | NumberPart::Integer
+            | NumberPart::Fraction
+            | NumberPart::ExponentDigits
+            | NumberPart::Hex => true,
+            NumberPart::Point => json5,
+            _ => false,
+        }
+    }
+
+    /// Only a sign has been read, so far. In JSON5 this may be followed by `Infinity` or `NaN`
+    pub(crate) fn is_sign(self) -> bool {
+        matches!(self, NumberPart::Sign)
+    }
+}
+
+/// The exact parts of a number, without any rounding from conversion to a float
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JSONDecimal<'a> {
+    pub negative: bool,
+    /// Digits before the decimal point. Can be empty for JSON5 numbers
+    pub integer: &'a str,
+    /// Digits after the decimal point. Empty if there is no fractional part
+    pub fraction: &'a str,
+    /// Saturates for exponents that do not fit
+    pub exponent: i64,
+}
+
+impl<'a> JSONDecimal<'a> {
+    /// Splits a decimal number. Expects it to have been validated by the parser, hexadecimal,
+    /// `Infinity` and `NaN` numbers are not handled
+    #[must_use]
+    pub fn new(number: &'a str) -> Self {
+        let (negative, rest) = split_sign(number);
+        let (mantissa, exponent) = rest.split_once(['e', 'E']).unwrap_or((rest, ""));
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+        let (exponent_negative, digits) = split_sign(exponent);
+        let exponent = digits.bytes().fold(0i64, |acc, digit| {
+            let digit = i64::from(digit - b'0');
+            let digit = if exponent_negative { -digit } else { digit };
+            acc.saturating_mul(10).saturating_add(digit)
+        });
+
+        Self {
+            negative,
+            integer,
+            fraction,
+            exponent,
+        }
+    }
+
+    /// Whether the number has no fractional part or exponent
+    #[must_use]
+    pub fn is_integer(&self) -> bool {
+        self.fraction.is_empty() && self.exponent == 0
+    }
+}
+
+fn split_sign(on: &str) -> (bool, &str) {
+    match on.strip_prefix(['+', '-']) {
+        Some(rest) => (on.starts_with('-'), rest),
+        None => (false, on),
+    }
+}
+
+fn hex_digits(number: &str) -> Option<(bool, &str)> {
+    let (negative, rest) = split_sign(number);
+    let digits = rest
+        .strip_prefix("0x")
+        .or_else(|| rest.strip_prefix("0X"))?;
+    Some((negative, digits))
+}
+
+/// Decimal numbers without a fraction or exponent and (JSON5) hexadecimal numbers
+pub(crate) fn parse_integer(number: &str) -> Option<i128> {
+    let (negative, value) = if let Some((negative, digits)) = hex_digits(number) {
+        (negative, u128::from_str_radix(digits, 16).ok()?)
+    } else {
+        let (negative, digits) = split_sign(number);
+        if !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+            return None;
+        }
+        (negative, digits.parse().ok()?)
+    };
+    let value = i128::try_from(value).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn parse_float(number: &str) -> Option<f64> {
+    if hex_digits(number).is_some() {
+        parse_integer(number).map(|value| value as f64)
+    } else {
+        number.parse().ok()
+    }
+}
+
+/// Whether [`JSONDecimal::new`] can be used
+pub(crate) fn is_decimal(number: &str) -> bool {
+    hex_digits(number).is_none()
+        && split_sign(number)
+            .1
+            .starts_with(|chr: char| chr.is_ascii_digit() || chr == '.')
+}
+
+/// The number in the JSON grammar: without a leading `+`, with digits either side of a decimal
+/// point and hexadecimal written in decimal. `None` for `Infinity` and `NaN`, which JSON cannot
+/// represent
+pub(crate) fn to_json(number: &str) -> Option<Cow<'_, str>> {
+    if let Some((negative, digits)) = hex_digits(number) {
+        let sign = if negative { "-" } else { "" };
+        return Some(Cow::Owned(format!("{sign}{}", hex_to_decimal(digits))));
+    }
+    if !is_decimal(number) {
+        return None;
+    }
+    let (negative, rest) = split_sign(number);
+    let (mantissa, exponent) = rest.split_at(rest.find(['e', 'E']).unwrap_or(rest.len()));
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if !number.starts_with('+') && !integer.is_empty() && !mantissa.ends_with('.') {
+        return Some(Cow::Borrowed(number));
+    }
+
+    let mut json = String::with_capacity(number.len() + 1);
+    if negative {
+        json.push('-');
+    }
+    json.push_str(if integer.is_empty() { "0" } else { integer });
+    if !fraction.is_empty() {
+        json.push('.');
+        json.push_str(fraction);
+    }
+    json.push_str(exponent);
+    Some(Cow::Owned(json))
+}
+
+/// Exact for any number of digits
+fn hex_to_decimal(hex: &str) -> String {
+    // Lowest first
+    let mut decimal: Vec<u32> = Vec::new();
+    for digit in hex.chars().filter_map(|chr| chr.to_digit(16)) {
+        let mut carry = digit;
+        for place in &mut decimal {
+            let value = *place * 16 + carry;
+            *place = value % 10;
+            carry = value / 10;
+        }
+        while carry > 0 {
+            decimal.push(carry % 10);
+            carry /= 10;
+        }
+    }
+    let mut digits: String = decimal
+        .iter()
+        .rev()
+        .filter_map(|digit| char::from_digit(*digit, 10))
+        .collect();
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    digits
+}
@@ -0,0 +1,89 @@
+use std::{fmt, io::BufRead};
+
+use crate::{JSONKey, JSONParseError, JSONStreamParser, ParseOptions, RootJSONValue};
+
+/// Error from [`parse_reader`]
+#[derive(Debug)]
+pub enum JSONReadError {
+    /// Reading failed after `at` bytes
+    IO {
+        at: usize,
+        error: std::io::Error,
+    },
+    Parse(JSONParseError),
+}
+
+impl JSONReadError {
+    /// Byte offset in the input
+    #[must_use]
+    pub fn at(&self) -> usize {
+        match self {
+            JSONReadError::IO { at, .. } => *at,
+            JSONReadError::Parse(error) => error.at,
+        }
+    }
+}
+
+impl std::error::Error for JSONReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JSONReadError::IO { error, .. } => Some(error),
+            JSONReadError::Parse(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for JSONReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JSONReadError::IO { at, error } => {
+                f.write_fmt(format_args!("JSONReadError: {error} at {at:?}"))
+            }
+            JSONReadError::Parse(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl From<JSONParseError> for JSONReadError {
+    fn from(error: JSONParseError) -> Self {
+        JSONReadError::Parse(error)
+    }
+}
+
+/// Parses from a file, stdin etc without reading it into memory first. `cb` returning `true` stops
+/// reading
+///
+/// # Errors
+/// Returns an error if reading fails or the input is invalid JSON
+pub fn parse_reader(
+    reader: impl BufRead,
+    cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+) -> Result<(), JSONReadError> {
+    parse_reader_with_options(reader, ParseOptions::default(), cb)
+}
+
+/// [`parse_reader`] with a choice of which extensions to accept
+///
+/// # Errors
+/// Returns an error if reading fails or the input is invalid under `options`
+pub fn parse_reader_with_options(
+    mut reader: impl BufRead,
+    options: ParseOptions,
+    cb: impl for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+) -> Result<(), JSONReadError> {
+    let mut parser = JSONStreamParser::with_options(options, cb);
+    let mut at = 0;
+    while !parser.is_finished() {
+        let chunk = match reader.fill_buf() {
+            Ok([]) => break,
+            Ok(chunk) => chunk,
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(JSONReadError::IO { at, error }),
+        };
+        parser.feed(chunk)?;
+        let length = chunk.len();
+        reader.consume(length);
+        at += length;
+    }
+    Ok(parser.finish()?)
+}
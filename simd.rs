@@ -0,0 +1,277 @@
+//! Classifying the input in blocks of 64 bytes, in the style of the first stage of
+//! [simdjson](https://arxiv.org/abs/1902.08318). Where the quotes, backslashes, whitespace and
+//! structural characters are is found for a whole block at once, then the parser skips to the next
+//! character it has to look at using the bit masks. Only used with the `simd` feature
+
+/// Implementation picked for the current CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Scanner {
+    /// Every character goes through the state machine
+    Scalar,
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    Sse2,
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    Avx2,
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+    Neon,
+}
+
+impl Scanner {
+    /// `simd` is [`crate::ParseOptions::simd`]
+    pub(crate) fn new(simd: bool) -> Self {
+        if !simd {
+            return Scanner::Scalar;
+        }
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        {
+            #[cfg(feature = "std")]
+            let avx2 = std::is_x86_feature_detected!("avx2");
+            #[cfg(not(feature = "std"))]
+            let avx2 = cfg!(target_feature = "avx2");
+            if avx2 {
+                Scanner::Avx2
+            } else {
+                Scanner::Sse2
+            }
+        }
+        #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+        {
+            Scanner::Neon
+        }
+        #[cfg(not(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64"))))]
+        {
+            Scanner::Scalar
+        }
+    }
+
+    fn classify(self, block: &[u8; 64]) -> Block {
+        match self {
+            Scanner::Scalar => classify(block),
+            // SAFETY: SSE2 is always available on x86_64
+            #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+            Scanner::Sse2 => unsafe { x86_64::classify_sse2(block) },
+            // SAFETY: only picked when AVX2 is available
+            #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+            Scanner::Avx2 => unsafe { x86_64::classify_avx2(block) },
+            // SAFETY: NEON is always available on aarch64
+            #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+            Scanner::Neon => unsafe { aarch64::classify_neon(block) },
+        }
+    }
+}
+
+/// Where each kind of character is in a block, as a bit for each byte (the lowest for the first)
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Block {
+    pub(crate) double_quote: u64,
+    pub(crate) single_quote: u64,
+    pub(crate) backslash: u64,
+    /// Bytes less than `' '`
+    pub(crate) control: u64,
+    /// ` `, `\t`, `\n` and `\r`
+    pub(crate) whitespace: u64,
+    /// `{`, `}`, `[`, `]`, `:` and `,`
+    pub(crate) structural: u64,
+}
+
+/// The classified block the parser is in. Searches are answered from it until they pass its end
+#[derive(Debug)]
+pub(crate) struct StructuralIndex {
+    scanner: Scanner,
+    /// Of the block in the input
+    start: usize,
+    /// Less than 64 at the end of the input
+    length: usize,
+    block: Block,
+}
+
+impl StructuralIndex {
+    /// `simd` is [`crate::ParseOptions::simd`]
+    pub(crate) fn new(simd: bool) -> Self {
+        Self {
+            scanner: Scanner::new(simd),
+            start: 0,
+            length: 0,
+            block: Block::default(),
+        }
+    }
+
+    pub(crate) fn is_scalar(&self) -> bool {
+        self.scanner == Scanner::Scalar
+    }
+
+    /// The offset of the first byte from `idx` that is set in `mask` of its block, or the end of `on`
+    pub(crate) fn find(
+        &mut self,
+        on: &[u8],
+        mut idx: usize,
+        mask: impl Fn(&Block) -> u64,
+    ) -> usize {
+        while idx < on.len() {
+            if !(self.start..self.start + self.length).contains(&idx) {
+                self.classify(on, idx);
+            }
+            let in_block = if self.length == 64 {
+                u64::MAX
+            } else {
+                (1 << self.length) - 1
+            };
+            let found = (mask(&self.block) & in_block) >> (idx - self.start);
+            if found != 0 {
+                return idx + found.trailing_zeros() as usize;
+            }
+            idx = self.start + self.length;
+        }
+        on.len()
+    }
+
+    fn classify(&mut self, on: &[u8], start: usize) {
+        let rest = &on[start..];
+        self.start = start;
+        self.length = rest.len().min(64);
+        self.block = if let Some(block) = rest.first_chunk() {
+            self.scanner.classify(block)
+        } else {
+            // Past the end is not looked at
+            let mut block = [0; 64];
+            block[..rest.len()].copy_from_slice(rest);
+            self.scanner.classify(&block)
+        };
+    }
+}
+
+fn classify(block: &[u8; 64]) -> Block {
+    let mut classified = Block::default();
+    for (idx, &byte) in block.iter().enumerate() {
+        let bit = |matches: bool| u64::from(matches) << idx;
+        classified.double_quote |= bit(byte == b'"');
+        classified.single_quote |= bit(byte == b'\'');
+        classified.backslash |= bit(byte == b'\\');
+        classified.control |= bit(byte < b' ');
+        classified.whitespace |= bit(matches!(byte, b' ' | b'\t' | b'\n' | b'\r'));
+        classified.structural |= bit(matches!(byte, b'{' | b'}' | b'[' | b']' | b':' | b','));
+    }
+    classified
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+#[allow(clippy::cast_ptr_alignment)] // `loadu` does not need aligned pointers
+mod x86_64 {
+    use core::arch::x86_64::{
+        __m128i, __m256i, _mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_max_epu8,
+        _mm256_movemask_epi8, _mm256_or_si256, _mm256_set1_epi8, _mm_cmpeq_epi8, _mm_loadu_si128,
+        _mm_max_epu8, _mm_movemask_epi8, _mm_or_si128, _mm_set1_epi8,
+    };
+
+    use super::Block;
+
+    /// Bytes less than `' '` are those where `max(byte, 0x1f) == 0x1f`
+    const CONTROL: i8 = 0x1f;
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn classify_sse2(block: &[u8; 64]) -> Block {
+        let mut classified = Block::default();
+        for (idx, chunk) in block.chunks_exact(16).enumerate() {
+            // SAFETY: `chunk` is 16 bytes
+            let bytes = unsafe { _mm_loadu_si128(chunk.as_ptr().cast::<__m128i>()) };
+            let eq = |byte: u8| _mm_cmpeq_epi8(bytes, _mm_set1_epi8(byte.cast_signed()));
+            let bits = |found: __m128i| {
+                u64::from(_mm_movemask_epi8(found).cast_unsigned() & 0xffff) << (16 * idx)
+            };
+            let control = _mm_set1_epi8(CONTROL);
+
+            classified.double_quote |= bits(eq(b'"'));
+            classified.single_quote |= bits(eq(b'\''));
+            classified.backslash |= bits(eq(b'\\'));
+            classified.control |= bits(_mm_cmpeq_epi8(_mm_max_epu8(bytes, control), control));
+            classified.whitespace |= bits(_mm_or_si128(
+                _mm_or_si128(eq(b' '), eq(b'\t')),
+                _mm_or_si128(eq(b'\n'), eq(b'\r')),
+            ));
+            classified.structural |= bits(_mm_or_si128(
+                _mm_or_si128(
+                    _mm_or_si128(eq(b'{'), eq(b'}')),
+                    _mm_or_si128(eq(b'['), eq(b']')),
+                ),
+                _mm_or_si128(eq(b':'), eq(b',')),
+            ));
+        }
+        classified
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn classify_avx2(block: &[u8; 64]) -> Block {
+        let mut classified = Block::default();
+        for (idx, chunk) in block.chunks_exact(32).enumerate() {
+            // SAFETY: `chunk` is 32 bytes
+            let bytes = unsafe { _mm256_loadu_si256(chunk.as_ptr().cast::<__m256i>()) };
+            let eq = |byte: u8| _mm256_cmpeq_epi8(bytes, _mm256_set1_epi8(byte.cast_signed()));
+            let bits = |found: __m256i| {
+                u64::from(_mm256_movemask_epi8(found).cast_unsigned()) << (32 * idx)
+            };
+            let control = _mm256_set1_epi8(CONTROL);
+
+            classified.double_quote |= bits(eq(b'"'));
+            classified.single_quote |= bits(eq(b'\''));
+            classified.backslash |= bits(eq(b'\\'));
+            classified.control |= bits(_mm256_cmpeq_epi8(_mm256_max_epu8(bytes, control), control));
+            classified.whitespace |= bits(_mm256_or_si256(
+                _mm256_or_si256(eq(b' '), eq(b'\t')),
+                _mm256_or_si256(eq(b'\n'), eq(b'\r')),
+            ));
+            classified.structural |= bits(_mm256_or_si256(
+                _mm256_or_si256(
+                    _mm256_or_si256(eq(b'{'), eq(b'}')),
+                    _mm256_or_si256(eq(b'['), eq(b']')),
+                ),
+                _mm256_or_si256(eq(b':'), eq(b',')),
+            ));
+        }
+        classified
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+mod aarch64 {
+    use core::arch::aarch64::{
+        uint8x16_t, vaddv_u8, vandq_u8, vceqq_u8, vcltq_u8, vdupq_n_u8, vget_high_u8, vget_low_u8,
+        vld1q_u8, vorrq_u8,
+    };
+
+    use super::Block;
+
+    /// NEON has no `movemask`, so each matching lane is given its bit and the halves are summed
+    const BITS: [u8; 16] = [1, 2, 4, 8, 16, 32, 64, 128, 1, 2, 4, 8, 16, 32, 64, 128];
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn classify_neon(block: &[u8; 64]) -> Block {
+        // SAFETY: `BITS` is 16 bytes
+        let weights = unsafe { vld1q_u8(BITS.as_ptr()) };
+        let mut classified = Block::default();
+        for (idx, chunk) in block.chunks_exact(16).enumerate() {
+            // SAFETY: `chunk` is 16 bytes
+            let bytes = unsafe { vld1q_u8(chunk.as_ptr()) };
+            let eq = |byte: u8| vceqq_u8(bytes, vdupq_n_u8(byte));
+            let bits = |found: uint8x16_t| {
+                let weighted = vandq_u8(found, weights);
+                let low = u64::from(vaddv_u8(vget_low_u8(weighted)));
+                let high = u64::from(vaddv_u8(vget_high_u8(weighted)));
+                (low | (high << 8)) << (16 * idx)
+            };
+
+            classified.double_quote |= bits(eq(b'"'));
+            classified.single_quote |= bits(eq(b'\''));
+            classified.backslash |= bits(eq(b'\\'));
+            classified.control |= bits(vcltq_u8(bytes, vdupq_n_u8(b' ')));
+            classified.whitespace |= bits(vorrq_u8(
+                vorrq_u8(eq(b' '), eq(b'\t')),
+                vorrq_u8(eq(b'\n'), eq(b'\r')),
+            ));
+            classified.structural |= bits(vorrq_u8(
+                vorrq_u8(vorrq_u8(eq(b'{'), eq(b'}')), vorrq_u8(eq(b'['), eq(b']'))),
+                vorrq_u8(eq(b':'), eq(b',')),
+            ));
+        }
+        classified
+    }
+}
@@ -0,0 +1,366 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+    vec::Vec,
+};
+
+use crate::{
+    Comment, JSONIterator, JSONKey, JSONParseError, JSONParseErrorReason, KeyChain, KeyVec,
+    ParseOptions, RootJSONValue, Span, State, StructuralIndex,
+};
+
+/// A [`JSONKey`] that outlives the part of the buffer it was read from
+enum OwnedKey {
+    Slice(String),
+    Index(usize),
+}
+
+impl OwnedKey {
+    fn as_key(&self) -> JSONKey<'_> {
+        match self {
+            OwnedKey::Slice(key) => JSONKey::Slice(key),
+            OwnedKey::Index(index) => JSONKey::Index(*index),
+        }
+    }
+}
+
+/// The key chain while parsing the buffer. Keys from earlier calls are only converted once they
+/// are popped, changed or needed for a callback, so that a call does not copy the whole chain
+struct StreamKeys<'a> {
+    earlier: &'a [OwnedKey],
+    /// The length of the start of `earlier` that is still in the chain, unchanged
+    unchanged: usize,
+    /// Whether `keys` starts with the unchanged keys, rather than the keys after them
+    materialized: bool,
+    keys: KeyVec<'a>,
+}
+
+impl<'a> StreamKeys<'a> {
+    /// Adds the unchanged keys to `keys`, so that [`KeyChain::keys`] is the whole chain
+    fn materialize(&mut self) {
+        if !self.materialized {
+            self.materialized = true;
+            let earlier = &self.earlier[..self.unchanged];
+            self.keys
+                .keys
+                .splice(0..0, earlier.iter().map(OwnedKey::as_key));
+            // Key spans are not reported by the stream parser
+            self.keys
+                .spans
+                .splice(0..0, earlier.iter().map(|_| Span { start: 0, end: 0 }));
+        }
+    }
+
+    /// The keys after the unchanged ones
+    fn changed(&self) -> &[JSONKey<'a>] {
+        let start = if self.materialized { self.unchanged } else { 0 };
+        &self.keys.keys[start..]
+    }
+}
+
+impl<'a> KeyChain<'a> for StreamKeys<'a> {
+    fn push(&mut self, key: JSONKey<'a>, span: Span) -> bool {
+        self.keys.push(key, span)
+    }
+
+    fn pop(&mut self) {
+        if self.materialized || !self.keys.is_empty() {
+            self.keys.pop();
+            self.unchanged = self.unchanged.min(self.len());
+        } else {
+            self.unchanged = self.unchanged.saturating_sub(1);
+        }
+    }
+
+    /// Only the whole chain after [`StreamKeys::materialize`]
+    fn keys(&self) -> &[JSONKey<'a>] {
+        self.keys.keys()
+    }
+
+    fn spans(&self) -> &[Span] {
+        self.keys.spans()
+    }
+
+    fn last_mut(&mut self) -> Option<(&mut JSONKey<'a>, &mut Span)> {
+        if self.materialized {
+            self.unchanged = self.unchanged.min(self.len().saturating_sub(1));
+        } else if self.keys.is_empty() && self.unchanged > 0 {
+            self.unchanged -= 1;
+            let key = self.earlier[self.unchanged].as_key();
+            self.keys.push(key, Span { start: 0, end: 0 });
+        }
+        self.keys.last_mut()
+    }
+
+    fn len(&self) -> usize {
+        if self.materialized {
+            self.keys.len()
+        } else {
+            self.unchanged + self.keys.len()
+        }
+    }
+
+    fn last(&self) -> Option<JSONKey<'a>> {
+        self.keys
+            .last()
+            .or_else(|| self.earlier[..self.unchanged].last().map(OwnedKey::as_key))
+    }
+}
+
+/// Push based parser for input that arrives in chunks, for example from a socket. Calls `cb` as
+/// values are completed, with the same arguments as [`crate::parse_with_exit_signal`]. `cb`
+/// returning `true` stops parsing.
+///
+/// Input is buffered from the start of the current token, so values split across chunks are
+/// reported whole. [`JSONParseError::at`] is the offset from the start of the first chunk
+pub struct JSONStreamParser<F> {
+    options: ParseOptions,
+    cb: F,
+    /// Input from the start of the current token
+    buffer: String,
+    /// The start of a UTF-8 sequence split across chunks
+    incomplete: Vec<u8>,
+    /// Of `buffer` in the whole input
+    offset: usize,
+    idx: usize,
+    state: State,
+    comment: Option<Comment>,
+    key_chain: Vec<OwnedKey>,
+    push_index: bool,
+    values: usize,
+    finished: bool,
+}
+
+impl<F> JSONStreamParser<F>
+where
+    F: for<'b> FnMut(&'b [JSONKey<'b>], RootJSONValue<'b>) -> bool,
+{
+    #[must_use]
+    pub fn new(cb: F) -> Self {
+        Self::with_options(ParseOptions::default(), cb)
+    }
+
+    #[must_use]
+    pub fn with_options(options: ParseOptions, cb: F) -> Self {
+        Self {
+            options,
+            cb,
+            buffer: String::new(),
+            incomplete: Vec::new(),
+            offset: 0,
+            idx: 0,
+            state: State::ExpectingValue { after_comma: false },
+            comment: None,
+            key_chain: Vec::new(),
+            push_index: false,
+            values: 0,
+            finished: false,
+        }
+    }
+
+    /// Whether parsing has stopped, after an error or `cb` returning `true`. Further input is ignored
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Parses `chunk`, calling `cb` for the values it completes
+    ///
+    /// # Errors
+    /// Returns an error if the input so far is invalid. With [`ParseOptions::recover`] the rest of
+    /// the chunk is still parsed and the first error in it is returned. [`crate::parse_with_recovery`]
+    /// returns every error
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), JSONParseError> {
+        if self.finished {
+            return Ok(());
+        }
+        let invalid = self.push(chunk);
+        self.run(true)?;
+        match invalid {
+            Some(at) if !self.finished => {
+                self.finished = true;
+                Err(JSONParseError {
+                    at,
+                    reason: JSONParseErrorReason::InvalidUTF8,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Marks the end of the input, reporting a value at the end of it (a number at the root)
+    ///
+    /// # Errors
+    /// Returns an error if the input is incomplete or invalid. With [`ParseOptions::recover`] the
+    /// rest of the input is still parsed and the first error in it is returned
+    pub fn finish(mut self) -> Result<(), JSONParseError> {
+        if self.finished {
+            return Ok(());
+        }
+        if !self.incomplete.is_empty() {
+            self.run(true)?;
+            return Err(JSONParseError {
+                at: self.offset + self.buffer.len(),
+                reason: JSONParseErrorReason::InvalidUTF8,
+            });
+        }
+        self.run(false)
+    }
+
+    /// Appends the valid UTF-8 part of `chunk` to the buffer. Returns the offset of invalid UTF-8
+    fn push(&mut self, chunk: &[u8]) -> Option<usize> {
+        let bytes = if self.incomplete.is_empty() {
+            Cow::Borrowed(chunk)
+        } else {
+            let mut bytes = core::mem::take(&mut self.incomplete);
+            bytes.extend_from_slice(chunk);
+            Cow::Owned(bytes)
+        };
+        match core::str::from_utf8(&bytes) {
+            Ok(text) => {
+                self.buffer.push_str(text);
+                None
+            }
+            Err(error) => {
+                let (valid, rest) = bytes.split_at(error.valid_up_to());
+                self.buffer
+                    .push_str(core::str::from_utf8(valid).unwrap_or_default());
+                if error.error_len().is_some() {
+                    Some(self.offset + self.buffer.len())
+                } else {
+                    self.incomplete = rest.to_vec();
+                    None
+                }
+            }
+        }
+    }
+
+    /// Parses the buffer and then drops the part of it before the current token. `partial` is
+    /// whether more input can follow
+    fn run(&mut self, partial: bool) -> Result<(), JSONParseError> {
+        let mut iterator = JSONIterator {
+            on: self.buffer.as_bytes(),
+            text: Some(&self.buffer),
+            options: self.options,
+            index: StructuralIndex::new(self.options.simd),
+            idx: self.idx,
+            state: core::mem::replace(&mut self.state, State::EndOfValue),
+            comment: self.comment.take(),
+            keys: StreamKeys {
+                earlier: &self.key_chain,
+                unchanged: self.key_chain.len(),
+                materialized: false,
+                keys: KeyVec::default(),
+            },
+            span: Span { start: 0, end: 0 },
+            push_index: self.push_index,
+            values: self.values,
+            finished: false,
+            partial,
+            raw_strings: false,
+        };
+
+        let mut result = Ok(());
+        while let Some(value) = iterator.next_value() {
+            match value {
+                Ok(value) => {
+                    iterator.keys.materialize();
+                    if (self.cb)(iterator.key_chain(), value) {
+                        self.finished = true;
+                        break;
+                    }
+                }
+                // Parsing continues after the error if recovering
+                Err(error) if result.is_ok() => {
+                    result = Err(JSONParseError {
+                        at: self.offset + error.at,
+                        reason: error.reason,
+                    });
+                }
+                Err(_) => {}
+            }
+        }
+
+        let JSONIterator {
+            idx,
+            state,
+            comment,
+            keys,
+            push_index,
+            values,
+            finished,
+            ..
+        } = iterator;
+        let changed: Vec<OwnedKey> = keys
+            .changed()
+            .iter()
+            .map(|key| match *key {
+                JSONKey::Slice(key) => OwnedKey::Slice(key.to_owned()),
+                JSONKey::Index(index) => OwnedKey::Index(index),
+            })
+            .collect();
+        self.key_chain.truncate(keys.unchanged);
+        self.key_chain.extend(changed);
+        self.idx = idx;
+        self.state = state;
+        self.comment = comment;
+        self.push_index = push_index;
+        self.values = values;
+        self.finished |= finished;
+
+        self.compact();
+        result
+    }
+
+    /// Drops the buffer up to the start of the current token
+    fn compact(&mut self) {
+        let start = [
+            Some(self.idx),
+            token_start(&self.state),
+            self.comment.as_ref().map(|comment| comment.start),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(self.idx);
+
+        if start == 0 {
+            return;
+        }
+        self.buffer.drain(..start);
+        self.offset += start;
+        self.idx -= start;
+        rebase(&mut self.state, start);
+        if let Some(ref mut comment) = self.comment {
+            comment.start -= start;
+        }
+    }
+}
+
+/// The offset of the token `state` is in the middle of. Strings include their opening quote
+fn token_start(state: &State) -> Option<usize> {
+    match *state {
+        State::InKey { start, quote, .. } | State::StringValue { start, quote, .. } => {
+            Some(start - quote.len_utf8())
+        }
+        State::IdentifierKey { start, .. }
+        | State::NumberValue { start, .. }
+        | State::Literal { start } => Some(start),
+        State::Colon
+        | State::InObject { .. }
+        | State::ExpectingValue { .. }
+        | State::EndOfValue
+        | State::Recover { .. } => None,
+    }
+}
+
+fn rebase(state: &mut State, by: usize) {
+    if let State::InKey { start, .. }
+    | State::IdentifierKey { start, .. }
+    | State::StringValue { start, .. }
+    | State::NumberValue { start, .. }
+    | State::Literal { start } = state
+    {
+        *start -= by;
+    }
+}
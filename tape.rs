@@ -0,0 +1,275 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{
+    unescape, JSONEvent, JSONIterator, JSONKey, JSONParseError, ParseOptions, RootJSONValue,
+};
+
+/// A whole JSON document as a flat list of nodes, in the order they appear. Containers store where
+/// they end, so skipping over them does not visit their contents. Quicker to build and to read
+/// from than [`crate::Value`], for documents that are queried many times
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tape<'a> {
+    nodes: Vec<TapeNode<'a>>,
+}
+
+/// Strings and keys are the raw contents between the quotes, see [`unescape`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapeNode<'a> {
+    /// `end` is the index of the node after the object. `len` is the number of members
+    Object {
+        end: usize,
+        len: usize,
+    },
+    /// `end` is the index of the node after the array. `len` is the number of items
+    Array {
+        end: usize,
+        len: usize,
+    },
+    /// Before each value in an object
+    Key(&'a str),
+    String(&'a str),
+    Number(&'a str),
+    True,
+    False,
+    Null,
+}
+
+impl<'a> Tape<'a> {
+    /// # Errors
+    /// Returns an error if `on` is not valid JSON or is empty
+    pub fn parse(on: &'a str) -> Result<Self, JSONParseError> {
+        Self::parse_with_options(on, ParseOptions::default())
+    }
+
+    /// [`Tape::parse`] with a choice of which extensions to accept. Stops at the first error, even
+    /// with [`ParseOptions::recover`]
+    ///
+    /// # Errors
+    /// Returns an error if `on` is invalid under `options` or is empty
+    pub fn parse_with_options(on: &'a str, options: ParseOptions) -> Result<Self, JSONParseError> {
+        let mut iterator = JSONIterator::with_options(on, options);
+        let mut nodes = Vec::new();
+        // Indexes of containers that have been started but not ended
+        let mut open: Vec<usize> = Vec::new();
+        while let Some(event) = iterator.next_event() {
+            let node = match event? {
+                JSONEvent::ObjectStart => TapeNode::Object { end: 0, len: 0 },
+                JSONEvent::ArrayStart => TapeNode::Array { end: 0, len: 0 },
+                JSONEvent::ObjectEnd | JSONEvent::ArrayEnd => {
+                    let after = nodes.len();
+                    if let Some(TapeNode::Object { end, .. } | TapeNode::Array { end, .. }) =
+                        open.pop().map(|idx| &mut nodes[idx])
+                    {
+                        *end = after;
+                    }
+                    continue;
+                }
+                JSONEvent::Value(RootJSONValue::String(value)) => TapeNode::String(value),
+                JSONEvent::Value(RootJSONValue::Number(value)) => TapeNode::Number(value),
+                JSONEvent::Value(RootJSONValue::True) => TapeNode::True,
+                JSONEvent::Value(RootJSONValue::False) => TapeNode::False,
+                JSONEvent::Value(RootJSONValue::Null) => TapeNode::Null,
+                JSONEvent::Comment(_) => continue,
+            };
+            if let Some(parent) = open.last().map(|idx| &mut nodes[*idx]) {
+                let in_object = matches!(parent, TapeNode::Object { .. });
+                if let TapeNode::Object { len, .. } | TapeNode::Array { len, .. } = parent {
+                    *len += 1;
+                }
+                if let (true, Some(JSONKey::Slice(key))) = (in_object, iterator.key_chain().last())
+                {
+                    nodes.push(TapeNode::Key(key));
+                }
+            }
+            if let TapeNode::Object { .. } | TapeNode::Array { .. } = node {
+                open.push(nodes.len());
+            }
+            nodes.push(node);
+        }
+        iterator.expect_value()?;
+        Ok(Self { nodes })
+    }
+
+    #[must_use]
+    pub fn root(&self) -> TapeRef<'_, 'a> {
+        TapeRef { tape: self, idx: 0 }
+    }
+
+    #[must_use]
+    pub fn nodes(&self) -> &[TapeNode<'a>] {
+        &self.nodes
+    }
+}
+
+/// A value in a [`Tape`]
+#[derive(Debug, Clone, Copy)]
+pub struct TapeRef<'t, 'a> {
+    tape: &'t Tape<'a>,
+    idx: usize,
+}
+
+impl<'t, 'a> TapeRef<'t, 'a> {
+    #[must_use]
+    pub fn node(&self) -> TapeNode<'a> {
+        self.tape.nodes[self.idx]
+    }
+
+    /// Position in [`Tape::nodes`]
+    #[must_use]
+    pub fn index_in_tape(&self) -> usize {
+        self.idx
+    }
+
+    /// Index of the node after this value and its contents
+    fn next_sibling(&self) -> usize {
+        match self.node() {
+            TapeNode::Object { end, .. } | TapeNode::Array { end, .. } => end,
+            _ => self.idx + 1,
+        }
+    }
+
+    fn at(&self, idx: usize) -> Self {
+        Self {
+            tape: self.tape,
+            idx,
+        }
+    }
+
+    /// The value of the last member named `key`, if an object. Skips over the values of other
+    /// members
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Self> {
+        self.raw_members()?
+            .filter(|(name, _)| {
+                // Only keys with escapes are unescaped to compare
+                if name.contains('\\') {
+                    unescape(name) == key
+                } else {
+                    *name == key
+                }
+            })
+            .last()
+            .map(|(_, value)| value)
+    }
+
+    /// The item at `index`, if an array. Skips over the items before it
+    #[must_use]
+    pub fn index(&self, index: usize) -> Option<Self> {
+        self.items()?.nth(index)
+    }
+
+    /// Members of an object, with their keys unescaped
+    #[must_use]
+    pub fn members(&self) -> Option<impl Iterator<Item = (Cow<'a, str>, Self)> + 't> {
+        Some(
+            self.raw_members()?
+                .map(|(key, value)| (unescape(key), value)),
+        )
+    }
+
+    /// Members of an object, with their keys as in the input
+    fn raw_members(&self) -> Option<impl Iterator<Item = (&'a str, Self)> + 't> {
+        let TapeNode::Object { end, .. } = self.node() else {
+            return None;
+        };
+        let this = *self;
+        let mut idx = self.idx + 1;
+        Some(core::iter::from_fn(move || {
+            if idx >= end {
+                return None;
+            }
+            let TapeNode::Key(key) = this.tape.nodes[idx] else {
+                unreachable!("members start with a key")
+            };
+            let value = this.at(idx + 1);
+            idx = value.next_sibling();
+            Some((key, value))
+        }))
+    }
+
+    /// Items of an array
+    #[must_use]
+    pub fn items(&self) -> Option<impl Iterator<Item = Self> + 't> {
+        let TapeNode::Array { end, .. } = self.node() else {
+            return None;
+        };
+        let this = *self;
+        let mut idx = self.idx + 1;
+        Some(core::iter::from_fn(move || {
+            if idx >= end {
+                return None;
+            }
+            let item = this.at(idx);
+            idx = item.next_sibling();
+            Some(item)
+        }))
+    }
+
+    /// Number of members or items, if an object or array
+    #[must_use]
+    pub fn len(&self) -> Option<usize> {
+        match self.node() {
+            TapeNode::Object { len, .. } | TapeNode::Array { len, .. } => Some(len),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// `None` for objects and arrays
+    #[must_use]
+    pub fn value(&self) -> Option<RootJSONValue<'a>> {
+        match self.node() {
+            TapeNode::String(value) => Some(RootJSONValue::String(value)),
+            TapeNode::Number(value) => Some(RootJSONValue::Number(value)),
+            TapeNode::True => Some(RootJSONValue::True),
+            TapeNode::False => Some(RootJSONValue::False),
+            TapeNode::Null => Some(RootJSONValue::Null),
+            TapeNode::Object { .. } | TapeNode::Array { .. } | TapeNode::Key(_) => None,
+        }
+    }
+
+    /// Unescaped, if a string
+    #[must_use]
+    pub fn as_str(&self) -> Option<Cow<'a, str>> {
+        match self.node() {
+            TapeNode::String(value) => Some(unescape(value)),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.node() {
+            TapeNode::True => Some(true),
+            TapeNode::False => Some(false),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_null(&self) -> bool {
+        self.node() == TapeNode::Null
+    }
+
+    /// See [`RootJSONValue::as_i64`]
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        self.value()?.as_i64()
+    }
+
+    /// See [`RootJSONValue::as_u64`]
+    #[must_use]
+    pub fn as_u64(&self) -> Option<u64> {
+        self.value()?.as_u64()
+    }
+
+    /// See [`RootJSONValue::as_f64`]
+    #[must_use]
+    pub fn as_f64(&self) -> Option<f64> {
+        self.value()?.as_f64()
+    }
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "async")]
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+use simple_json_parser::{
+    parse_async_reader, parse_stream, try_parse_stream, JSONKey, JSONParseError,
+    JSONParseErrorReason, JSONReadError,
+};
+
+/// Yields each chunk, returning pending in between
+struct Chunks<'a, T> {
+    chunks: std::slice::Iter<'a, T>,
+    pending: bool,
+}
+
+impl<T: Copy> Stream for Chunks<'_, T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.pending = !self.pending;
+        if self.pending {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        } else {
+            Poll::Ready(self.chunks.next().copied())
+        }
+    }
+}
+
+fn chunks<T>(chunks: &[T]) -> Chunks<'_, T> {
+    Chunks {
+        chunks: chunks.iter(),
+        pending: false,
+    }
+}
+
+#[tokio::test]
+async fn stream_of_chunks() {
+    let content: &[&[u8]] = &[
+        b"{ \"na",
+        b"me\": \"simple-",
+        b"json-parser\", \"ver",
+        b"sion\": 4 }",
+    ];
+
+    let mut values = Vec::new();
+    parse_stream(chunks(content), |keys, value| {
+        let Some(JSONKey::Slice(key)) = keys.first() else {
+            panic!()
+        };
+        values.push(format!("{key} {value:?}"));
+        false
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+        values,
+        [
+            r#"name String("simple-json-parser")"#,
+            r#"version Number("4")"#
+        ]
+    );
+}
+
+#[tokio::test]
+async fn stream_exit_and_errors() {
+    let content: &[&[u8]] = &[b"[1, 2, ", b"3, x]"];
+
+    let mut count = 0;
+    parse_stream(chunks(content), |_, _| {
+        count += 1;
+        true
+    })
+    .await
+    .unwrap();
+    assert_eq!(count, 1);
+
+    let result = parse_stream(chunks(content), |_, _| false).await;
+    assert_eq!(
+        result,
+        Err(JSONParseError {
+            at: 10,
+            reason: JSONParseErrorReason::ExpectedValue
+        })
+    );
+}
+
+#[tokio::test]
+async fn stream_of_results() {
+    let content: &[Result<&[u8], &str>] = &[Ok(b"[1, 2"), Ok(b", 3]")];
+    let mut count = 0;
+    try_parse_stream(chunks(content), |_, _| {
+        count += 1;
+        false
+    })
+    .await
+    .unwrap();
+    assert_eq!(count, 3);
+
+    let content: &[Result<&[u8], &str>] = &[Ok(b"[1, 2"), Err("connection reset"), Ok(b"]")];
+    let result = try_parse_stream(chunks(content), |_, _| false).await;
+    let Err(JSONReadError::IO { at, error }) = result else {
+        panic!("expected stream error, found {result:?}");
+    };
+    assert_eq!(at, 5);
+    assert_eq!(error.to_string(), "connection reset");
+
+    let content: &[Result<&[u8], &str>] = &[Ok(b"[1, x")];
+    let result = try_parse_stream(chunks(content), |_, _| false).await;
+    let Err(JSONReadError::Parse(error)) = result else {
+        panic!("expected parse error, found {result:?}");
+    };
+    assert_eq!(error.at, 4);
+}
+
+#[tokio::test]
+async fn async_reader() {
+    let content = br#"{ "a": [true, false], "b": { "c": null } }"#;
+
+    let mut paths = Vec::new();
+    parse_async_reader(content.as_slice(), |keys, _| {
+        paths.push(keys.len());
+        false
+    })
+    .await
+    .unwrap();
+    assert_eq!(paths, [2, 2, 2]);
+
+    let result = parse_async_reader(br#"{ "a": tru }"#.as_slice(), |_, _| false).await;
+    let Err(JSONReadError::Parse(error)) = result else {
+        panic!("expected parse error, found {result:?}");
+    };
+    assert_eq!(error.at, 10);
+}
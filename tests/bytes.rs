@@ -0,0 +1,131 @@
+use simple_json_parser::{
+    parse_bytes, parse_bytes_raw, parse_with_options, JSONEvent, JSONIterator, JSONKey,
+    JSONParseError, JSONParseErrorReason, ParseOptions, RawJSONValue, RootJSONValue,
+};
+
+fn values(on: &[u8], options: ParseOptions) -> Result<Vec<String>, JSONParseError> {
+    let mut values = Vec::new();
+    parse_bytes(on, options, |keys, value| {
+        values.push(format!("{keys:?} {value:?}"));
+    })?;
+    Ok(values)
+}
+
+#[test]
+fn same_as_str() {
+    let content = r#"{
+        "ascii": "value", "ünïcödé": "😀 ✓", # comment ✓
+        "numbers": [1, -2.5e3], "literals": [true, false, null],
+        /* ✓ */ "nested": { "a": [[]] }
+    }"#;
+
+    let mut expected = Vec::new();
+    parse_with_options(content, ParseOptions::default(), |keys, value| {
+        expected.push(format!("{keys:?} {value:?}"));
+    })
+    .unwrap();
+
+    assert_eq!(
+        values(content.as_bytes(), ParseOptions::default()).unwrap(),
+        expected
+    );
+
+    let json5 = "{ ünquoted: 'single', hex: 0xFF, nan: -NaN,\u{a0}trailing: [1,], }";
+    let mut expected = Vec::new();
+    parse_with_options(json5, ParseOptions::json5(), |keys, value| {
+        expected.push(format!("{keys:?} {value:?}"));
+    })
+    .unwrap();
+    assert_eq!(
+        values(json5.as_bytes(), ParseOptions::json5()).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn invalid_utf8_in_strings() {
+    assert_eq!(
+        values(b"[\"ok\", \"bad \xff\"]", ParseOptions::default()),
+        Err(JSONParseError {
+            at: 12,
+            reason: JSONParseErrorReason::InvalidUTF8
+        })
+    );
+    // Truncated sequence in a key
+    assert_eq!(
+        values(b"{ \"\xe2\x9c\": 1 }", ParseOptions::default()),
+        Err(JSONParseError {
+            at: 3,
+            reason: JSONParseErrorReason::InvalidUTF8
+        })
+    );
+    // Outside of strings
+    assert_eq!(
+        values(b"[1,\xc0 2]", ParseOptions::default()),
+        Err(JSONParseError {
+            at: 3,
+            reason: JSONParseErrorReason::InvalidUTF8
+        })
+    );
+}
+
+#[test]
+fn comments_only_checked_when_reported() {
+    let content = b"[1, // \xff\n 2]";
+    assert_eq!(values(content, ParseOptions::default()).unwrap().len(), 2);
+
+    let options = ParseOptions {
+        comment_events: true,
+        ..ParseOptions::default()
+    };
+    let mut iterator = JSONIterator::from_bytes(content, options);
+    let mut events = Vec::new();
+    while let Some(event) = iterator.next_event() {
+        events.push(event.map(|event| matches!(event, JSONEvent::Comment(_))));
+    }
+    assert_eq!(
+        events.last(),
+        Some(&Err(JSONParseError {
+            at: 7,
+            reason: JSONParseErrorReason::InvalidUTF8
+        }))
+    );
+}
+
+#[test]
+fn raw_strings() {
+    let content = b"{ \"key\": [\"\xff\xfe\", 'a\\n', 1] }";
+    let mut values = Vec::new();
+    let result = parse_bytes_raw(content, ParseOptions::json5(), |keys, value| {
+        values.push((keys.to_vec(), value));
+    });
+    assert!(result.is_ok(), "{result:?}");
+
+    assert_eq!(
+        values,
+        vec![
+            (
+                vec![JSONKey::Slice("key"), JSONKey::Index(0)],
+                RawJSONValue::String(b"\xff\xfe")
+            ),
+            (
+                vec![JSONKey::Slice("key"), JSONKey::Index(1)],
+                RawJSONValue::String(b"a\\n")
+            ),
+            (
+                vec![JSONKey::Slice("key"), JSONKey::Index(2)],
+                RawJSONValue::Other(RootJSONValue::Number("1"))
+            ),
+        ]
+    );
+
+    // Keys are still checked
+    let result = parse_bytes_raw(b"{ \"\xff\": 1 }", ParseOptions::default(), |_, _| {});
+    assert_eq!(
+        result,
+        Err(JSONParseError {
+            at: 3,
+            reason: JSONParseErrorReason::InvalidUTF8
+        })
+    );
+}
@@ -0,0 +1,94 @@
+use simple_json_parser::{
+    CommentKind, JSONComment, JSONEvent, JSONIterator, JSONKey, ParseOptions, Span,
+};
+
+fn comments(content: &str) -> Vec<(Vec<JSONKey<'_>>, JSONComment<'_>)> {
+    let options = ParseOptions {
+        comment_events: true,
+        ..ParseOptions::default()
+    };
+    let mut iterator = JSONIterator::with_options(content, options);
+    let mut comments = Vec::new();
+    while let Some(event) = iterator.next_event() {
+        if let JSONEvent::Comment(comment) = event.unwrap() {
+            comments.push((iterator.key_chain().to_vec(), comment));
+        }
+    }
+    comments
+}
+
+#[test]
+fn kinds_and_spans() {
+    let content = "// top\n{ /* block */ \"a\": 1 # hash\n}";
+
+    assert_eq!(
+        comments(content),
+        vec![
+            (
+                vec![],
+                JSONComment {
+                    text: " top",
+                    kind: CommentKind::Line,
+                    span: Span { start: 0, end: 6 }
+                }
+            ),
+            (
+                vec![],
+                JSONComment {
+                    text: " block ",
+                    kind: CommentKind::Block,
+                    span: Span { start: 9, end: 20 }
+                }
+            ),
+            (
+                vec![JSONKey::Slice("a")],
+                JSONComment {
+                    text: " hash",
+                    kind: CommentKind::Hash,
+                    span: Span { start: 28, end: 34 }
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn attached_paths() {
+    let content = r#"{
+        "compilerOptions": {
+            // deprecated
+            "out": "dist",
+            "strict": true // required
+        },
+        "files": [
+            "a.ts", // first
+            "b.ts"
+        ]
+    } // end"#;
+
+    let found = comments(content)
+        .into_iter()
+        .map(|(keys, comment)| (keys, comment.text))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        found,
+        vec![
+            (vec![JSONKey::Slice("compilerOptions")], " deprecated"),
+            (
+                vec![JSONKey::Slice("compilerOptions"), JSONKey::Slice("strict")],
+                " required"
+            ),
+            (vec![JSONKey::Slice("files"), JSONKey::Index(1)], " first"),
+            (vec![], " end"),
+        ]
+    );
+}
+
+#[test]
+fn not_reported_by_default() {
+    let events = JSONIterator::new("[1 /* a */, // b\n 2]")
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    assert_eq!(events.len(), 2);
+}
@@ -0,0 +1,149 @@
+use simple_json_parser::{
+    CommentKind, JSONParseErrorReason, ParseOptions, RootJSONValue, SyntaxNode, SyntaxToken,
+    SyntaxTree, TokenKind,
+};
+
+#[test]
+fn round_trip() {
+    let documents = [
+        "{}",
+        " [ ] ",
+        "\r\n\t42\r\n",
+        "// header\n{ \"a\" /* after key */ : /* before value */ 1 , \"b\":[true,false,null,],}\n# end",
+        "{\"nested\": {\"deeper\": [[], {}, [{\"x\": \"y\\n\"}]]}}",
+        "[1, 2, 3] // no new line at the end",
+        "{\"unicode\": \"é😀\",\u{a0}\"space\": 1}",
+    ];
+    for document in documents {
+        let tree = SyntaxTree::parse(document).unwrap();
+        assert_eq!(tree.to_string(), document);
+        // Tokens cover the input without gaps
+        let mut at = 0;
+        for token in tree.tokens() {
+            assert_eq!(token.span.start, at, "{document:?}");
+            assert_eq!(&document[token.span.start..token.span.end], token.text);
+            at = token.span.end;
+        }
+        assert_eq!(at, document.len());
+    }
+
+    let json5 = "{unquoted: 'single', hex: 0xFF, +Infinity: .5, trailing: [1,],}";
+    let tree = SyntaxTree::parse_with_options(json5, ParseOptions::json5()).unwrap();
+    assert_eq!(tree.to_string(), json5);
+}
+
+#[test]
+fn json_test_suite() {
+    for entry in std::fs::read_dir("tests/json_test_suite").unwrap() {
+        let path = entry.unwrap().path();
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        for options in [ParseOptions::strict(), ParseOptions::default()] {
+            if let Ok(tree) = SyntaxTree::parse_with_options(&content, options) {
+                assert_eq!(tree.to_string(), content, "{}", path.display());
+            }
+        }
+    }
+}
+
+#[test]
+fn tokens() {
+    let content = "{ \"a\" /* c */ : [1,] }";
+    let tree = SyntaxTree::parse(content).unwrap();
+    let kinds: Vec<_> = tree.tokens().map(|token| token.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            TokenKind::OpenBrace,
+            TokenKind::Whitespace,
+            TokenKind::Key,
+            TokenKind::Whitespace,
+            TokenKind::Comment(CommentKind::Block),
+            TokenKind::Whitespace,
+            TokenKind::Colon,
+            TokenKind::Whitespace,
+            TokenKind::OpenBracket,
+            TokenKind::Number,
+            TokenKind::Comma,
+            TokenKind::CloseBracket,
+            TokenKind::Whitespace,
+            TokenKind::CloseBrace,
+        ]
+    );
+
+    let root = tree.root().unwrap();
+    assert_eq!(root.span().start, 0);
+    assert_eq!(root.span().end, content.len());
+    let array = root.get("a").unwrap();
+    assert!(matches!(array, SyntaxNode::Array(children) if children.len() == 4));
+    assert_eq!(
+        array.index(0).unwrap().value(),
+        Some(RootJSONValue::Number("1"))
+    );
+}
+
+#[test]
+fn editing() {
+    let tsconfig = r#"{
+    // Compiler options
+    "compilerOptions": {
+        "target": "es2020", /* keep in sync with the runtime */
+        "strict": false,
+        "paths": ["./src/*"]
+    }
+}
+"#;
+    let mut tree = SyntaxTree::parse(tsconfig).unwrap();
+    let options = tree.root_mut().unwrap().get_mut("compilerOptions").unwrap();
+
+    let SyntaxNode::Token(strict) = options.get_mut("strict").unwrap() else {
+        panic!()
+    };
+    strict.kind = TokenKind::True;
+    strict.text = "true".into();
+
+    let path = options.get_mut("paths").unwrap().index_mut(0).unwrap();
+    *path = SyntaxNode::Token(SyntaxToken {
+        kind: TokenKind::String,
+        text: format!("\"{}\"", "./lib/*").into(),
+        span: path.span(),
+    });
+
+    assert_eq!(
+        tree.to_string(),
+        r#"{
+    // Compiler options
+    "compilerOptions": {
+        "target": "es2020", /* keep in sync with the runtime */
+        "strict": true,
+        "paths": ["./lib/*"]
+    }
+}
+"#
+    );
+
+    let root = tree.root().unwrap();
+    let keys: Vec<_> = root
+        .get("compilerOptions")
+        .unwrap()
+        .members()
+        .unwrap()
+        .map(|(key, _)| key.into_owned())
+        .collect();
+    assert_eq!(keys, ["target", "strict", "paths"]);
+    assert!(root.items().is_none());
+    assert!(root.get("missing").is_none());
+}
+
+#[test]
+fn errors() {
+    let error = SyntaxTree::parse("{ \"a\": }").unwrap_err();
+    assert_eq!(error.reason, JSONParseErrorReason::ExpectedValue);
+
+    let error = SyntaxTree::parse(" // only a comment").unwrap_err();
+    assert_eq!(
+        (error.at, error.reason),
+        (18, JSONParseErrorReason::ExpectedValue)
+    );
+}
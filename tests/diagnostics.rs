@@ -0,0 +1,48 @@
+use simple_json_parser::{line_column, parse, LineColumn};
+
+#[test]
+fn resolves_line_and_column() {
+    let content = "{\n  \"é\": 1,\n\t\"b\" 2\n}";
+    assert_eq!(line_column(content, 0), LineColumn { line: 1, column: 1 });
+    assert_eq!(line_column(content, 4), LineColumn { line: 2, column: 3 });
+    // After the two byte `é`
+    assert_eq!(line_column(content, 7), LineColumn { line: 2, column: 5 });
+    assert_eq!(line_column(content, 100), LineColumn { line: 4, column: 2 });
+}
+
+#[test]
+fn renders_diagnostic() {
+    let content = "{\n  \"a\": 1,\n\t\"b\" 2\n}";
+    let error = parse(content, |_, _| {}).unwrap_err();
+
+    assert_eq!(
+        error.line_column(content),
+        LineColumn { line: 3, column: 6 }
+    );
+    assert_eq!(
+        error.diagnostic(content).to_string(),
+        "error: missing `:` between key and value
+ --> 3:6
+  |
+3 | \t\"b\" 2
+  | \t    ^ expected `:`"
+    );
+    assert_eq!(
+        error.to_string(),
+        "JSONParseError: missing `:` between key and value at 17"
+    );
+}
+
+#[test]
+fn renders_end_of_input() {
+    let content = "[1, 2";
+    let error = parse(content, |_, _| {}).unwrap_err();
+    assert_eq!(
+        error.diagnostic(content).to_string(),
+        "error: unclosed object or array
+ --> 1:6
+  |
+1 | [1, 2
+  |      ^ expected `}` or `]`"
+    );
+}
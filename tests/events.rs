@@ -0,0 +1,106 @@
+use simple_json_parser::{parse_events, JSONEvent, JSONKey, RootJSONValue};
+
+fn collect(content: &str) -> Vec<(Vec<JSONKey<'_>>, JSONEvent<'_>)> {
+    let mut events = Vec::new();
+    let result = parse_events(content, |keys, event| events.push((keys.to_vec(), event)));
+    assert!(result.is_ok(), "{result:?}");
+    events
+}
+
+#[test]
+fn empty_containers() {
+    assert_eq!(
+        collect(r#"{ "a": {}, "b": [], "c": [{}, []] }"#),
+        vec![
+            (vec![], JSONEvent::ObjectStart),
+            (vec![JSONKey::Slice("a")], JSONEvent::ObjectStart),
+            (vec![JSONKey::Slice("a")], JSONEvent::ObjectEnd),
+            (vec![JSONKey::Slice("b")], JSONEvent::ArrayStart),
+            (vec![JSONKey::Slice("b")], JSONEvent::ArrayEnd),
+            (vec![JSONKey::Slice("c")], JSONEvent::ArrayStart),
+            (
+                vec![JSONKey::Slice("c"), JSONKey::Index(0)],
+                JSONEvent::ObjectStart
+            ),
+            (
+                vec![JSONKey::Slice("c"), JSONKey::Index(0)],
+                JSONEvent::ObjectEnd
+            ),
+            (
+                vec![JSONKey::Slice("c"), JSONKey::Index(1)],
+                JSONEvent::ArrayStart
+            ),
+            (
+                vec![JSONKey::Slice("c"), JSONKey::Index(1)],
+                JSONEvent::ArrayEnd
+            ),
+            (vec![JSONKey::Slice("c")], JSONEvent::ArrayEnd),
+            (vec![], JSONEvent::ObjectEnd),
+        ]
+    );
+}
+
+#[test]
+fn container_sizes() {
+    let content = r#"{ "items": [1, 2, { "a": true, "b": null }], "other": "x" }"#;
+
+    let mut sizes = Vec::new();
+    let mut stack = Vec::new();
+    let result = parse_events(content, |keys, event| match event {
+        JSONEvent::ObjectStart | JSONEvent::ArrayStart => {
+            if let Some(count) = stack.last_mut() {
+                *count += 1;
+            }
+            stack.push(0);
+        }
+        JSONEvent::ObjectEnd | JSONEvent::ArrayEnd => {
+            sizes.push((keys.to_vec(), stack.pop().unwrap()));
+        }
+        JSONEvent::Value(_) => {
+            if let Some(count) = stack.last_mut() {
+                *count += 1;
+            }
+        }
+        JSONEvent::Comment(_) => {}
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(
+        sizes,
+        vec![
+            (vec![JSONKey::Slice("items"), JSONKey::Index(2)], 2),
+            (vec![JSONKey::Slice("items")], 3),
+            (vec![], 2),
+        ]
+    );
+}
+
+#[test]
+fn leaves_match_parse() {
+    let content = r#"[[1, "two"], { "three": false }]"#;
+    let values: Vec<_> = collect(content)
+        .into_iter()
+        .filter_map(|(keys, event)| match event {
+            JSONEvent::Value(value) => Some((keys, value)),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        values,
+        vec![
+            (
+                vec![JSONKey::Index(0), JSONKey::Index(0)],
+                RootJSONValue::Number("1")
+            ),
+            (
+                vec![JSONKey::Index(0), JSONKey::Index(1)],
+                RootJSONValue::String("two")
+            ),
+            (
+                vec![JSONKey::Index(1), JSONKey::Slice("three")],
+                RootJSONValue::False
+            ),
+        ]
+    );
+}
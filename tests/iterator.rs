@@ -0,0 +1,70 @@
+use simple_json_parser::{JSONIterator, JSONKey, RootJSONValue};
+
+#[test]
+fn values_with_paths() {
+    let content = r#"{
+        "name": "simple-json-parser",
+        "version": 4,
+        "keywords": ["json", "parser"],
+        "private": false
+    }"#;
+
+    let items = JSONIterator::new(content).collect::<Result<Vec<_>, _>>();
+
+    assert_eq!(
+        items.unwrap(),
+        vec![
+            (
+                vec![JSONKey::Slice("name")],
+                RootJSONValue::String("simple-json-parser")
+            ),
+            (vec![JSONKey::Slice("version")], RootJSONValue::Number("4")),
+            (
+                vec![JSONKey::Slice("keywords"), JSONKey::Index(0)],
+                RootJSONValue::String("json")
+            ),
+            (
+                vec![JSONKey::Slice("keywords"), JSONKey::Index(1)],
+                RootJSONValue::String("parser")
+            ),
+            (vec![JSONKey::Slice("private")], RootJSONValue::False),
+        ]
+    );
+}
+
+#[test]
+fn numbers_before_delimiters() {
+    let mut iterator = JSONIterator::new("[1, 2,3]");
+    let mut indexes = Vec::new();
+    while let Some(value) = iterator.next_value() {
+        let RootJSONValue::Number(number) = value.unwrap() else {
+            panic!("expected number")
+        };
+        indexes.push((iterator.key_chain().to_vec(), number));
+    }
+
+    assert_eq!(
+        indexes,
+        vec![
+            (vec![JSONKey::Index(0)], "1"),
+            (vec![JSONKey::Index(1)], "2"),
+            (vec![JSONKey::Index(2)], "3"),
+        ]
+    );
+}
+
+#[test]
+fn find_and_error() {
+    let found = JSONIterator::new(r#"{ "a": 1, "b": "here", "c": nope }"#)
+        .find(|item| matches!(item, Ok((keys, _)) if keys == &[JSONKey::Slice("b")]));
+
+    assert!(matches!(
+        found,
+        Some(Ok((_, RootJSONValue::String("here"))))
+    ));
+
+    let mut iterator = JSONIterator::new(r#"{ "a": 1, "b": nope }"#);
+    assert!(iterator.next().unwrap().is_ok());
+    assert!(iterator.next().unwrap().is_err());
+    assert!(iterator.next().is_none());
+}
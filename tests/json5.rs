@@ -0,0 +1,120 @@
+use simple_json_parser::{
+    parse, parse_with_options, JSONKey, JSONParseErrorReason, ParseOptions, RootJSONValue,
+};
+
+#[test]
+fn json5_document() {
+    // From https://spec.json5.org/#introduction
+    let content = r#"{
+        // comments
+        unquoted: 'and you can quote me on that',
+        singleQuotes: 'I can use "double quotes" here',
+        lineBreaks: "Look, Mom! \
+No \\n's!",
+        hexadecimal: 0xdecaf,
+        leadingDecimalPoint: .8675309, andTrailing: 8675309.,
+        positiveSign: +1,
+        trailingComma: 'in objects', andIn: ['arrays',],
+        "backwardsCompatible": "with JSON",
+        $special_key2: -Infinity,
+        'quoted key': NaN,
+    }"#;
+
+    let mut values = Vec::new();
+    let result = parse_with_options(content, ParseOptions::json5(), |keys, value| {
+        values.push((keys.to_vec(), value));
+    });
+    assert!(result.is_ok(), "{result:?}");
+
+    let key = |key| vec![JSONKey::Slice(key)];
+    assert_eq!(
+        values,
+        vec![
+            (
+                key("unquoted"),
+                RootJSONValue::String("and you can quote me on that")
+            ),
+            (
+                key("singleQuotes"),
+                RootJSONValue::String(r#"I can use "double quotes" here"#)
+            ),
+            (
+                key("lineBreaks"),
+                RootJSONValue::String("Look, Mom! \\\nNo \\\\n's!")
+            ),
+            (key("hexadecimal"), RootJSONValue::Number("0xdecaf")),
+            (
+                key("leadingDecimalPoint"),
+                RootJSONValue::Number(".8675309")
+            ),
+            (key("andTrailing"), RootJSONValue::Number("8675309.")),
+            (key("positiveSign"), RootJSONValue::Number("+1")),
+            (key("trailingComma"), RootJSONValue::String("in objects")),
+            (
+                vec![JSONKey::Slice("andIn"), JSONKey::Index(0)],
+                RootJSONValue::String("arrays")
+            ),
+            (
+                key("backwardsCompatible"),
+                RootJSONValue::String("with JSON")
+            ),
+            (key("$special_key2"), RootJSONValue::Number("-Infinity")),
+            (key("quoted key"), RootJSONValue::Number("NaN")),
+        ]
+    );
+
+    assert_eq!(values[2].1.as_str().unwrap(), "Look, Mom! No \\n's!");
+    assert_eq!(values[3].1.as_i64(), Some(0xdecaf));
+    assert_eq!(values[4].1.as_f64(), Some(0.867_530_9));
+    assert_eq!(values[6].1.as_u64(), Some(1));
+    assert_eq!(values[10].1.as_f64(), Some(f64::NEG_INFINITY));
+    assert!(values[11].1.as_f64().unwrap().is_nan());
+    assert!(values[3].1.as_decimal().is_none());
+}
+
+#[test]
+fn json5_escapes() {
+    let content = r"['\x41\'\v\0', 'a\
+b']";
+    let mut strings = Vec::new();
+    let result = parse_with_options(content, ParseOptions::json5(), |_, value| {
+        strings.push(value.as_str().unwrap().into_owned());
+    });
+    assert!(result.is_ok(), "{result:?}");
+    assert_eq!(strings, ["A'\u{b}\0", "ab"]);
+
+    let content = r"{ \u0061b: 1, a\u0062: 2, $\u00e9: 3, '\0a': 4 }";
+    let mut keys = Vec::new();
+    let result = parse_with_options(content, ParseOptions::json5(), |path, _| {
+        keys.push(path[0].as_str().unwrap().into_owned());
+    });
+    assert!(result.is_ok(), "{result:?}");
+    assert_eq!(keys, ["ab", "ab", "$\u{e9}", "\0a"]);
+}
+
+#[test]
+fn json5_is_opt_in() {
+    for content in ["'a'", "0x10", "+1", ".5", "Infinity", "[1.]"] {
+        assert!(parse(content, |_, _| {}).is_err(), "{content}");
+    }
+}
+
+#[test]
+fn json5_errors() {
+    for (content, reason) in [
+        ("0x", JSONParseErrorReason::InvalidNumber),
+        ("-Infinite", JSONParseErrorReason::ExpectedTrueFalseNull),
+        ("'\\1'", JSONParseErrorReason::InvalidEscape),
+        ("'\\xg0'", JSONParseErrorReason::InvalidEscape),
+        ("{ a b: 1 }", JSONParseErrorReason::ExpectedColon),
+        ("'\\01'", JSONParseErrorReason::InvalidEscape),
+        ("{ \\x61: 1 }", JSONParseErrorReason::InvalidEscape),
+        ("{ a\\u00: 1 }", JSONParseErrorReason::InvalidEscape),
+        // Not valid as the first character, or at all
+        ("{ \\u0031: 1 }", JSONParseErrorReason::InvalidEscape),
+        ("{ a\\u002d: 1 }", JSONParseErrorReason::InvalidEscape),
+    ] {
+        let result = parse_with_options(content, ParseOptions::json5(), |_, _| {});
+        assert_eq!(result.map_err(|err| err.reason), Err(reason), "{content}");
+    }
+}
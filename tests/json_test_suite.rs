@@ -0,0 +1,135 @@
+//! Cases in the style of [JSONTestSuite](https://github.com/nst/JSONTestSuite), in
+//! `tests/json_test_suite`. Files starting `y_` must be accepted and `n_` rejected by
+//! [`ParseOptions::strict`]. `i_` files are left to the implementation by RFC 8259, the decisions
+//! for them are in [`IMPLEMENTATION_DEFINED`]
+
+use std::{fs, path::Path};
+
+use simple_json_parser::{parse_bytes, parse_events, JSONStreamParser, ParseOptions};
+
+/// Whether each `i_` file is accepted
+const IMPLEMENTATION_DEFINED: &[(&str, bool)] = &[
+    // Numbers are checked against the grammar only. Their range is up to the accessors:
+    // `as_i64` and `as_u64` return `None` and `as_f64` rounds to infinity or zero
+    ("i_number_double_huge_neg_exp", true),
+    ("i_number_huge_exp", true),
+    ("i_number_neg_int_huge_exp", true),
+    ("i_number_pos_double_huge_exp", true),
+    ("i_number_real_neg_overflow", true),
+    ("i_number_real_pos_overflow", true),
+    ("i_number_real_underflow", true),
+    ("i_number_too_big_neg_int", true),
+    ("i_number_too_big_pos_int", true),
+    ("i_number_very_big_negative_int", true),
+    // `\u` escapes of unpaired surrogates cannot be unescaped to a `str`, so are
+    // `InvalidEscape` errors
+    ("i_object_key_lone_2nd_surrogate", false),
+    ("i_string_1st_surrogate_but_2nd_missing", false),
+    ("i_string_1st_valid_surrogate_2nd_invalid", false),
+    ("i_string_incomplete_surrogate_and_escape_valid", false),
+    ("i_string_incomplete_surrogate_pair", false),
+    ("i_string_incomplete_surrogates_escape_valid", false),
+    ("i_string_invalid_lonely_surrogate", false),
+    ("i_string_invalid_surrogate", false),
+    ("i_string_inverted_surrogates_U+1D11E", false),
+    ("i_string_lone_second_surrogate", false),
+    // Input must be UTF-8 (RFC 8259 section 8.1), anything else is an `InvalidUTF8` error
+    ("i_string_UTF-16LE_with_BOM", false),
+    ("i_string_UTF-8_invalid_sequence", false),
+    ("i_string_UTF8_surrogate_U+D800", false),
+    ("i_string_invalid_utf-8", false),
+    ("i_string_iso_latin_1", false),
+    ("i_string_lone_utf8_continuation_byte", false),
+    ("i_string_not_in_unicode_range", false),
+    ("i_string_overlong_sequence_2_bytes", false),
+    ("i_string_overlong_sequence_6_bytes", false),
+    ("i_string_overlong_sequence_6_bytes_null", false),
+    ("i_string_truncated-utf-8", false),
+    ("i_string_utf16BE_no_BOM", false),
+    ("i_string_utf16LE_no_BOM", false),
+    // There is no nesting limit unless `ParseOptions::max_depth` is set
+    ("i_structure_500_nested_arrays", true),
+    // A byte order mark is not JSON whitespace, RFC 8259 allows rejecting it
+    ("i_structure_UTF-8_BOM_empty_object", false),
+];
+
+fn cases() -> Vec<(String, Vec<u8>)> {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/json_test_suite");
+    let mut cases: Vec<_> = fs::read_dir(directory)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_stem().unwrap().to_str().unwrap().to_owned();
+            (name, fs::read(path).unwrap())
+        })
+        .collect();
+    cases.sort();
+    cases
+}
+
+fn expected(name: &str) -> bool {
+    match name.as_bytes()[0] {
+        b'y' => true,
+        b'n' => false,
+        _ => {
+            IMPLEMENTATION_DEFINED
+                .iter()
+                .find(|(case, _)| *case == name)
+                .unwrap_or_else(|| panic!("no decision for {name}"))
+                .1
+        }
+    }
+}
+
+/// Runs each API over every case, returning the names of those with the wrong result
+fn mismatches(accepts: impl Fn(&[u8]) -> bool) -> Vec<String> {
+    cases()
+        .into_iter()
+        .filter(|(name, content)| accepts(content) != expected(name))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+#[test]
+fn parse_bytes_strict() {
+    let mismatches =
+        mismatches(|content| parse_bytes(content, ParseOptions::strict(), |_, _| {}).is_ok());
+    assert!(mismatches.is_empty(), "{mismatches:#?}");
+}
+
+#[test]
+fn events_strict() {
+    let mismatches = mismatches(|content| {
+        let Ok(content) = std::str::from_utf8(content) else {
+            return false;
+        };
+        let mut iterator =
+            simple_json_parser::JSONIterator::with_options(content, ParseOptions::strict());
+        std::iter::from_fn(|| iterator.next_event()).all(|event| event.is_ok())
+    });
+    assert!(mismatches.is_empty(), "{mismatches:#?}");
+
+    // Default options are a superset of strict
+    let content = cases();
+    for (name, content) in content.iter().filter(|(name, _)| name.starts_with("y_")) {
+        let content = std::str::from_utf8(content).unwrap();
+        assert!(parse_events(content, |_, _| {}).is_ok(), "{name}");
+    }
+}
+
+#[test]
+fn stream_strict_in_small_chunks() {
+    let mismatches = mismatches(|content| {
+        let mut parser = JSONStreamParser::with_options(ParseOptions::strict(), |_, _| false);
+        content.chunks(1).all(|chunk| parser.feed(chunk).is_ok()) && parser.finish().is_ok()
+    });
+    assert!(mismatches.is_empty(), "{mismatches:#?}");
+}
+
+#[test]
+fn every_decision_has_a_case() {
+    let cases = cases();
+    for (name, _) in IMPLEMENTATION_DEFINED {
+        assert!(cases.iter().any(|(case, _)| case == name), "{name}");
+    }
+}
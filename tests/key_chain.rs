@@ -0,0 +1,66 @@
+use simple_json_parser::{
+    JSONIterator, JSONKey, JSONParseError, JSONParseErrorReason, KeyArray, ParseOptions,
+    RootJSONValue, Span,
+};
+
+#[test]
+fn fixed_size_key_chain() {
+    let content = r#"{ "a": [1, { "b": true }], "c": null }"#;
+    let mut iterator =
+        JSONIterator::with_key_chain(content, ParseOptions::default(), KeyArray::<3>::new());
+
+    let mut values = Vec::new();
+    while let Some(value) = iterator.next_value() {
+        values.push((iterator.key_chain().to_vec(), value.unwrap()));
+    }
+
+    assert_eq!(
+        values,
+        vec![
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Index(0)],
+                RootJSONValue::Number("1")
+            ),
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Index(1), JSONKey::Slice("b")],
+                RootJSONValue::True
+            ),
+            (vec![JSONKey::Slice("c")], RootJSONValue::Null),
+        ]
+    );
+    assert_eq!(
+        iterator.key_spans(),
+        [] as [Span; 0],
+        "popped at the end of the document"
+    );
+}
+
+#[test]
+fn nesting_deeper_than_key_chain() {
+    let mut iterator =
+        JSONIterator::with_key_chain("[[[1]]]", ParseOptions::default(), KeyArray::<2>::new());
+    assert_eq!(
+        iterator.next_value(),
+        Some(Err(JSONParseError {
+            at: 3,
+            reason: JSONParseErrorReason::KeyChainFull
+        }))
+    );
+    assert_eq!(iterator.next_value(), None);
+
+    let content = r#"{ "a": { "b": 1 }, "c": 2 }"#;
+    let options = ParseOptions {
+        recover: true,
+        ..ParseOptions::default()
+    };
+    let mut iterator = JSONIterator::with_key_chain(content, options, KeyArray::<1>::new());
+    assert_eq!(
+        iterator.next_value(),
+        Some(Err(JSONParseError {
+            at: 9,
+            reason: JSONParseErrorReason::KeyChainFull
+        }))
+    );
+    // Not recovered from
+    assert_eq!(iterator.next_value(), None);
+}
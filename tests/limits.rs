@@ -0,0 +1,115 @@
+use simple_json_parser::{
+    parse_with_options, parse_with_recovery, JSONParseError, JSONParseErrorReason, ParseOptions,
+};
+
+fn first_error(content: &str, options: ParseOptions) -> Option<JSONParseError> {
+    parse_with_options(content, options, |_, _| {}).err()
+}
+
+#[test]
+fn max_depth() {
+    let options = ParseOptions {
+        max_depth: Some(2),
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(first_error(r#"{ "a": [1, 2], "b": {} }"#, options), None);
+    assert_eq!(
+        first_error(r#"{ "a": [1, [2]] }"#, options),
+        Some(JSONParseError {
+            at: 11,
+            reason: JSONParseErrorReason::MaxDepthExceeded
+        })
+    );
+
+    // Only a limit on nesting, not on the number of objects and arrays
+    let content = format!("[{}[]]", "[], ".repeat(1000));
+    assert_eq!(first_error(&content, options), None);
+
+    let content = "[".repeat(100_000);
+    assert_eq!(
+        first_error(&content, options),
+        Some(JSONParseError {
+            at: 2,
+            reason: JSONParseErrorReason::MaxDepthExceeded
+        })
+    );
+}
+
+#[test]
+fn max_string_length() {
+    let options = ParseOptions {
+        max_string_length: Some(4),
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(first_error(r#"{ "abcd": "\n\t" }"#, options), None);
+    assert_eq!(
+        first_error(r#"{ "abcde": 1 }"#, options),
+        Some(JSONParseError {
+            at: 7,
+            reason: JSONParseErrorReason::MaxStringLengthExceeded
+        })
+    );
+    // Escapes count towards the length
+    assert_eq!(
+        first_error(r#"["\n\t\""]"#, options),
+        Some(JSONParseError {
+            at: 6,
+            reason: JSONParseErrorReason::MaxStringLengthExceeded
+        })
+    );
+}
+
+#[test]
+fn max_number_length() {
+    let options = ParseOptions {
+        max_number_length: Some(5),
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(first_error("[-1.25, 12345]", options), None);
+    assert_eq!(
+        first_error("[1, 1e1000]", options),
+        Some(JSONParseError {
+            at: 9,
+            reason: JSONParseErrorReason::MaxNumberLengthExceeded
+        })
+    );
+}
+
+#[test]
+fn max_values() {
+    let options = ParseOptions {
+        max_values: Some(4),
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(first_error(r#"{ "a": [true, null] }"#, options), None);
+    assert_eq!(
+        first_error("[[], {}, 1, 2]", options),
+        Some(JSONParseError {
+            at: 12,
+            reason: JSONParseErrorReason::MaxValuesExceeded
+        })
+    );
+}
+
+#[test]
+fn limits_are_not_recovered_from() {
+    let options = ParseOptions {
+        max_string_length: Some(2),
+        ..ParseOptions::default()
+    };
+    let mut values = 0;
+    let errors = parse_with_recovery(r#"["a", "long", "b", x]"#, options, |_, _| values += 1);
+
+    assert_eq!(values, 1);
+    assert_eq!(
+        errors,
+        [JSONParseError {
+            at: 9,
+            reason: JSONParseErrorReason::MaxStringLengthExceeded
+        }]
+    );
+}
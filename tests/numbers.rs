@@ -0,0 +1,108 @@
+use simple_json_parser::{
+    parse, JSONDecimal, JSONIterator, JSONParseError, JSONParseErrorReason, RootJSONValue,
+};
+
+#[test]
+fn valid_numbers() {
+    let content = "[0, -0, 12, -3.25, 1e3, 2.5E-2, 6e+1, 18446744073709551615]";
+
+    let mut numbers = Vec::new();
+    let result = parse(content, |_, value| {
+        let RootJSONValue::Number(number) = value else {
+            panic!("expected number")
+        };
+        numbers.push(number);
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(
+        numbers,
+        [
+            "0",
+            "-0",
+            "12",
+            "-3.25",
+            "1e3",
+            "2.5E-2",
+            "6e+1",
+            "18446744073709551615"
+        ]
+    );
+}
+
+#[test]
+fn invalid_numbers() {
+    for (content, at) in [
+        ("1-2-3", 1),
+        ("01", 1),
+        ("1.e", 2),
+        ("[1.]", 3),
+        ("-", 1),
+        ("[-a]", 2),
+        ("1e", 2),
+        ("{ \"a\": 1x }", 8),
+    ] {
+        let result = parse(content, |_, _| {});
+        assert_eq!(
+            result,
+            Err(JSONParseError {
+                at,
+                reason: JSONParseErrorReason::InvalidNumber
+            }),
+            "{content}"
+        );
+    }
+}
+
+#[test]
+fn number_followed_by_a_value() {
+    for (content, at) in [
+        ("{\"a\":1\"b\":2}", 6),
+        ("[1\"a\"]", 2),
+        ("[1:2]", 2),
+        ("[1{}]", 2),
+        ("[1.5[]]", 4),
+        ("[true\"a\"]", 5),
+        ("[\"a\"1]", 4),
+    ] {
+        let result = parse(content, |_, _| {});
+        assert_eq!(
+            result,
+            Err(JSONParseError {
+                at,
+                reason: JSONParseErrorReason::ExpectedEndOfValue
+            }),
+            "{content}"
+        );
+    }
+}
+
+#[test]
+fn accessors() {
+    let values = JSONIterator::new("[-42, 18446744073709551615, 2.5, 1e2]")
+        .map(|item| item.unwrap().1)
+        .collect::<Vec<_>>();
+
+    assert_eq!(values[0].as_i64(), Some(-42));
+    assert_eq!(values[0].as_u64(), None);
+    assert_eq!(values[1].as_i64(), None);
+    assert_eq!(values[1].as_u64(), Some(u64::MAX));
+    assert_eq!(values[2].as_i64(), None);
+    assert_eq!(values[2].as_f64(), Some(2.5));
+    assert_eq!(values[3].as_f64(), Some(100.0));
+    assert_eq!(RootJSONValue::String("1").as_i64(), None);
+
+    assert_eq!(
+        RootJSONValue::Number("-12.500e-7").as_decimal(),
+        Some(JSONDecimal {
+            negative: true,
+            integer: "12",
+            fraction: "500",
+            exponent: -7
+        })
+    );
+    assert!(RootJSONValue::Number("3")
+        .as_decimal()
+        .unwrap()
+        .is_integer());
+}
@@ -0,0 +1,96 @@
+use simple_json_parser::{
+    parse, parse_with_options, JSONKey, JSONParseError, JSONParseErrorReason, ParseOptions,
+    RootJSONValue,
+};
+
+fn strict(content: &str) -> Result<(), JSONParseError> {
+    parse_with_options(content, ParseOptions::strict(), |_, _| {})
+}
+
+#[test]
+fn strict_accepts_json() {
+    let content = "{ \"a\": [1, true, null], \"b\": {}, \"c\": \"\u{a0}\" }\r\n";
+    assert!(strict(content).is_ok());
+}
+
+#[test]
+fn strict_rejects_extensions() {
+    for (content, at, reason) in [
+        ("// comment\n1", 0, JSONParseErrorReason::ExpectedValue),
+        ("# comment\n1", 0, JSONParseErrorReason::ExpectedValue),
+        (
+            "[1 /* comment */]",
+            3,
+            JSONParseErrorReason::ExpectedEndOfValue,
+        ),
+        ("[1,]", 3, JSONParseErrorReason::TrailingComma),
+        ("{ \"a\": 1, }", 10, JSONParseErrorReason::TrailingComma),
+        ("{ x \"a\": 1 }", 2, JSONParseErrorReason::ExpectedQuote),
+        (
+            "\"a\tb\"",
+            2,
+            JSONParseErrorReason::ControlCharacterInString,
+        ),
+        ("\u{a0}1", 0, JSONParseErrorReason::ExpectedValue),
+        ("", 0, JSONParseErrorReason::ExpectedValue),
+        ("  ", 2, JSONParseErrorReason::ExpectedValue),
+    ] {
+        assert_eq!(
+            strict(content),
+            Err(JSONParseError { at, reason }),
+            "{content:?}"
+        );
+    }
+}
+
+#[test]
+fn lenient_by_default() {
+    let content = "# config\n{ \"a\": [1, /* two */ 2,], // after\n \"b\": 3, }";
+
+    let mut values = Vec::new();
+    let result = parse(content, |keys, value| values.push((keys.to_vec(), value)));
+
+    assert!(result.is_ok(), "{result:?}");
+    assert_eq!(
+        values,
+        vec![
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Index(0)],
+                RootJSONValue::Number("1")
+            ),
+            (
+                vec![JSONKey::Slice("a"), JSONKey::Index(1)],
+                RootJSONValue::Number("2")
+            ),
+            (vec![JSONKey::Slice("b")], RootJSONValue::Number("3")),
+        ]
+    );
+}
+
+#[test]
+fn individual_toggles() {
+    let options = ParseOptions {
+        hash_comments: false,
+        ..ParseOptions::default()
+    };
+    assert!(parse_with_options("// ok\n1", options, |_, _| {}).is_ok());
+    assert!(parse_with_options("# not ok\n1", options, |_, _| {}).is_err());
+
+    let options = ParseOptions {
+        trailing_commas: true,
+        ..ParseOptions::strict()
+    };
+    assert!(parse_with_options("[1, 2,]", options, |_, _| {}).is_ok());
+}
+
+#[test]
+fn unterminated_comments() {
+    assert!(parse("1 // trailing", |_, _| {}).is_ok());
+    assert_eq!(
+        parse("1 /* trailing", |_, _| {}),
+        Err(JSONParseError {
+            at: 13,
+            reason: JSONParseErrorReason::ExpectedEndOfMultilineComment
+        })
+    );
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "std")]
+
+use std::io::{BufRead, BufReader, Read};
+
+use simple_json_parser::{
+    parse_reader, parse_reader_with_options, JSONKey, JSONParseError, JSONParseErrorReason,
+    JSONReadError, ParseOptions, RootJSONValue,
+};
+
+#[test]
+fn small_buffer() {
+    let content =
+        r#"{ "name": "simple-json-parser", "keywords": ["json", "parser"], "version": 4 }"#;
+    let reader = BufReader::with_capacity(3, content.as_bytes());
+
+    let mut values = Vec::new();
+    parse_reader(reader, |keys, value| {
+        let key = match keys.first() {
+            Some(JSONKey::Slice(key)) => (*key).to_owned(),
+            _ => String::new(),
+        };
+        values.push((key, format!("{value:?}")));
+        false
+    })
+    .unwrap();
+
+    assert_eq!(
+        values,
+        [
+            ("name", r#"String("simple-json-parser")"#),
+            ("keywords", r#"String("json")"#),
+            ("keywords", r#"String("parser")"#),
+            ("version", r#"Number("4")"#),
+        ]
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+    );
+}
+
+#[test]
+fn parse_errors_have_offsets() {
+    let reader = BufReader::with_capacity(4, br#"{ "a": [1, 2,] }"#.as_slice());
+    let result = parse_reader_with_options(reader, ParseOptions::strict(), |_, _| false);
+
+    let Err(JSONReadError::Parse(error)) = result else {
+        panic!("expected parse error, found {result:?}");
+    };
+    assert_eq!(
+        error,
+        JSONParseError {
+            at: 13,
+            reason: JSONParseErrorReason::TrailingComma
+        }
+    );
+}
+
+/// Fails after `remaining` bytes
+struct Failing<'a> {
+    content: &'a [u8],
+    remaining: usize,
+}
+
+impl Read for Failing<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(std::io::Error::other("connection reset"));
+        }
+        let length = buf.len().min(self.remaining).min(self.content.len());
+        buf[..length].copy_from_slice(&self.content[..length]);
+        self.content = &self.content[length..];
+        self.remaining -= length;
+        Ok(length)
+    }
+}
+
+#[test]
+fn io_errors_have_offsets() {
+    let reader = BufReader::with_capacity(
+        2,
+        Failing {
+            content: b"[1, 2, 3, 4]",
+            remaining: 5,
+        },
+    );
+    let mut values = 0;
+    let result = parse_reader(reader, |_, _| {
+        values += 1;
+        false
+    });
+
+    let Err(error @ JSONReadError::IO { .. }) = result else {
+        panic!("expected IO error, found {result:?}");
+    };
+    assert_eq!(error.at(), 5);
+    assert_eq!(values, 1);
+    assert!(error.to_string().contains("connection reset"));
+}
+
+#[test]
+fn exit_stops_reading() {
+    let mut reader = BufReader::with_capacity(4, b"[true, false, null, 1, 2, 3]".as_slice());
+    let mut first = None;
+    parse_reader(&mut reader, |_, value| {
+        first = Some(value == RootJSONValue::True);
+        true
+    })
+    .unwrap();
+
+    assert_eq!(first, Some(true));
+    // The rest of the input is not read
+    assert!(!reader.fill_buf().unwrap().is_empty());
+}
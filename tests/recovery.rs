@@ -0,0 +1,123 @@
+use simple_json_parser::{
+    parse_with_recovery, JSONEvent, JSONIterator, JSONKey, JSONParseError, JSONParseErrorReason,
+    ParseOptions, RootJSONValue,
+};
+
+fn recover(
+    content: &str,
+) -> (
+    Vec<(Vec<JSONKey<'_>>, RootJSONValue<'_>)>,
+    Vec<JSONParseError>,
+) {
+    let mut values = Vec::new();
+    let errors = parse_with_recovery(content, ParseOptions::strict(), |keys, value| {
+        values.push((keys.to_vec(), value));
+    });
+    (values, errors)
+}
+
+#[test]
+fn multiple_errors() {
+    let content = r#"{
+        "a": tru,
+        "b": 1,
+        "c": 01,
+        "d" "e",
+        "f": [1, x, 3],
+        "g": "\q, still in string",
+        "h": { "nested": nope, "other": 2 },
+        "i": true
+    }"#;
+
+    let (values, errors) = recover(content);
+
+    assert_eq!(
+        errors.iter().map(|error| error.reason).collect::<Vec<_>>(),
+        [
+            JSONParseErrorReason::ExpectedTrueFalseNull,
+            JSONParseErrorReason::InvalidNumber,
+            JSONParseErrorReason::ExpectedColon,
+            JSONParseErrorReason::ExpectedValue,
+            JSONParseErrorReason::InvalidEscape,
+            JSONParseErrorReason::ExpectedTrueFalseNull,
+        ]
+    );
+
+    assert_eq!(
+        values,
+        vec![
+            (vec![JSONKey::Slice("b")], RootJSONValue::Number("1")),
+            (
+                vec![JSONKey::Slice("f"), JSONKey::Index(0)],
+                RootJSONValue::Number("1")
+            ),
+            (
+                vec![JSONKey::Slice("f"), JSONKey::Index(2)],
+                RootJSONValue::Number("3")
+            ),
+            (
+                vec![JSONKey::Slice("h"), JSONKey::Slice("other")],
+                RootJSONValue::Number("2")
+            ),
+            (vec![JSONKey::Slice("i")], RootJSONValue::True),
+        ]
+    );
+}
+
+#[test]
+fn errors_in_keys_and_nested_skipping() {
+    let content = r#"{ "bad\key": { "skipped": [1, 2] }, x "also": 1, "good": [true] }"#;
+
+    let (values, errors) = recover(content);
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        values,
+        vec![(
+            vec![JSONKey::Slice("good"), JSONKey::Index(0)],
+            RootJSONValue::True
+        )]
+    );
+}
+
+#[test]
+fn structure_is_kept() {
+    let options = ParseOptions {
+        recover: true,
+        ..ParseOptions::strict()
+    };
+    let mut iterator = JSONIterator::with_options("[[1,], {\"a\": }, 2", options);
+    let mut events = Vec::new();
+    while let Some(event) = iterator.next_event() {
+        events.push(event.map(|event| (iterator.key_chain().to_vec(), event)));
+    }
+
+    let index = JSONKey::Index;
+    assert_eq!(
+        events,
+        vec![
+            Ok((vec![], JSONEvent::ArrayStart)),
+            Ok((vec![index(0)], JSONEvent::ArrayStart)),
+            Ok((
+                vec![index(0), index(0)],
+                JSONEvent::Value(RootJSONValue::Number("1"))
+            )),
+            Err(JSONParseError {
+                at: 4,
+                reason: JSONParseErrorReason::TrailingComma
+            }),
+            Ok((vec![index(0)], JSONEvent::ArrayEnd)),
+            Ok((vec![index(1)], JSONEvent::ObjectStart)),
+            Err(JSONParseError {
+                at: 13,
+                reason: JSONParseErrorReason::ExpectedValue
+            }),
+            Ok((vec![index(1)], JSONEvent::ObjectEnd)),
+            Ok((vec![index(2)], JSONEvent::Value(RootJSONValue::Number("2")))),
+            Err(JSONParseError {
+                at: 17,
+                reason: JSONParseErrorReason::ExpectedBracket
+            }),
+        ]
+    );
+}
@@ -0,0 +1,224 @@
+#![cfg(feature = "serde")]
+
+use std::{borrow::Cow, collections::HashMap};
+
+use serde::Deserialize;
+use simple_json_parser::{
+    from_str, from_str_with_options, JSONDeserializeError, JSONParseError, JSONParseErrorReason,
+    ParseOptions,
+};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct PackageJson<'a> {
+    name: &'a str,
+    version: &'a str,
+    #[serde(borrow)]
+    description: Option<Cow<'a, str>>,
+    private: bool,
+    #[serde(default)]
+    keywords: Vec<&'a str>,
+    #[serde(borrow)]
+    scripts: HashMap<&'a str, &'a str>,
+    workspaces: Option<Vec<String>>,
+}
+
+/// Any depth of arrays
+#[derive(Debug, Deserialize)]
+struct Nested(#[allow(dead_code)] Vec<Nested>);
+
+#[test]
+fn package_json() {
+    let content = r#"{
+        // comments are allowed
+        "name": "simple-json-parser",
+        "version": "0.0.4",
+        "description": "Parses \"JSON\"",
+        /* and so are trailing commas */
+        "private": false,
+        "scripts": { "build": "cargo build", "test": "cargo test", },
+        "workspaces": null,
+        "unknown": { "nested": [1, 2, { "ignored": true }] },
+    }"#;
+
+    let package: PackageJson = from_str(content).unwrap();
+    assert_eq!(
+        package,
+        PackageJson {
+            name: "simple-json-parser",
+            version: "0.0.4",
+            description: Some(Cow::Owned("Parses \"JSON\"".to_owned())),
+            private: false,
+            keywords: Vec::new(),
+            scripts: HashMap::from([("build", "cargo build"), ("test", "cargo test")]),
+            workspaces: None,
+        }
+    );
+
+    // Borrowed from the input
+    let start = content.as_ptr() as usize;
+    let end = start + content.len();
+    assert!((start..end).contains(&(package.name.as_ptr() as usize)));
+}
+
+#[test]
+fn escaped_borrow() {
+    // Escapes need to be unescaped into a new `String`, which `&str` cannot hold
+    let error = from_str::<&str>(r#""a\nb""#).unwrap_err();
+    assert!(matches!(error, JSONDeserializeError::Custom { at: 0, .. }));
+    assert_eq!(from_str::<String>(r#""a\nb""#).unwrap(), "a\nb");
+}
+
+#[test]
+fn values() {
+    assert_eq!(from_str::<u8>(" 200 ").unwrap(), 200);
+    assert_eq!(from_str::<i32>("-7").unwrap(), -7);
+    assert!((from_str::<f64>("1.5e3").unwrap() - 1500.0).abs() < f64::EPSILON);
+    assert!(from_str::<f64>("-0").unwrap().is_sign_negative());
+    // As with serde_json, `-0` is only a float
+    assert!(from_str::<i64>("-0").is_err());
+    assert_eq!(from_str::<i64>("-1").unwrap(), -1);
+    assert_eq!(from_str::<i64>(&i64::MIN.to_string()).unwrap(), i64::MIN);
+    assert_eq!(from_str::<u64>(&u64::MAX.to_string()).unwrap(), u64::MAX);
+    assert_eq!(from_str::<Option<u8>>("null").unwrap(), None);
+    assert_eq!(from_str::<Option<u8>>("1").unwrap(), Some(1));
+    assert_eq!(from_str::<()>("null").unwrap(), ());
+    assert_eq!(
+        from_str::<(bool, char, Vec<u8>)>("[true, \"c\", [1, 2]]").unwrap(),
+        (true, 'c', vec![1, 2])
+    );
+    assert_eq!(
+        from_str::<Vec<Vec<u8>>>("[[], [1], /* */ []]").unwrap(),
+        [vec![], vec![1], vec![]]
+    );
+}
+
+#[test]
+fn number_keys() {
+    let map = from_str::<HashMap<u32, &str>>(r#"{ "1": "a", "20": "b" }"#).unwrap();
+    assert_eq!(map, HashMap::from([(1, "a"), (20, "b")]));
+    let map = from_str::<HashMap<i8, bool>>(r#"{ "-1": true, "\u0032": false }"#).unwrap();
+    assert_eq!(map, HashMap::from([(-1, true), (2, false)]));
+
+    let content = r#"{ "1": "a", "b": "c" }"#;
+    let error = from_str::<HashMap<u32, &str>>(content).unwrap_err();
+    assert!(error.to_string().contains("invalid value"));
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Point(i32, i32),
+    Rectangle { width: u32, height: u32 },
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Meters(u32);
+
+#[test]
+fn enums_and_newtypes() {
+    let shapes: Vec<Shape> = from_str(
+        r#"[
+            "Empty",
+            { "Circle": 1.0 },
+            { "Point": [1, -1] },
+            { "Rectangle": { "width": 2, "height": 3 } }
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        shapes,
+        [
+            Shape::Empty,
+            Shape::Circle(1.0),
+            Shape::Point(1, -1),
+            Shape::Rectangle {
+                width: 2,
+                height: 3
+            }
+        ]
+    );
+
+    assert_eq!(from_str::<Meters>("5").unwrap(), Meters(5));
+}
+
+#[test]
+fn errors() {
+    // Parse errors are the same as the parser's
+    let error = from_str::<Vec<u8>>("[1, 2").unwrap_err();
+    assert_eq!(
+        error,
+        JSONDeserializeError::Parse(JSONParseError {
+            at: 5,
+            reason: JSONParseErrorReason::ExpectedBracket
+        })
+    );
+
+    let error = from_str::<u8>("").unwrap_err();
+    assert_eq!(
+        (error.at(), error),
+        (
+            0,
+            JSONDeserializeError::Parse(JSONParseError {
+                at: 0,
+                reason: JSONParseErrorReason::ExpectedValue
+            })
+        )
+    );
+
+    // Rather than overflowing the stack
+    let deep = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+    let error = from_str::<Nested>(&deep).unwrap_err();
+    assert_eq!(
+        error,
+        JSONDeserializeError::Parse(JSONParseError {
+            at: 128,
+            reason: JSONParseErrorReason::MaxDepthExceeded
+        })
+    );
+    let options = ParseOptions {
+        max_depth: Some(2),
+        ..ParseOptions::default()
+    };
+    assert!(from_str_with_options::<Vec<Vec<u8>>>("[[1]]", options).is_ok());
+    let error = from_str_with_options::<Vec<Vec<Vec<u8>>>>("[[[1]]]", options).unwrap_err();
+    assert!(matches!(
+        error,
+        JSONDeserializeError::Parse(JSONParseError {
+            reason: JSONParseErrorReason::MaxDepthExceeded,
+            ..
+        })
+    ));
+
+    // After the value
+    let error = from_str::<u8>("1 2").unwrap_err();
+    assert!(matches!(error, JSONDeserializeError::Parse(_)));
+
+    let error =
+        from_str_with_options::<Vec<u8>>("[1, // comment\n2]", ParseOptions::strict()).unwrap_err();
+    assert!(matches!(error, JSONDeserializeError::Parse(_)));
+
+    // Errors from the types point at the value
+    let content = r#"{ "name": "a", "version": 1, "private": true, "scripts": {} }"#;
+    let error = from_str::<PackageJson>(content).unwrap_err();
+    assert_eq!(error.at(), content.find('1').unwrap());
+    assert!(error.to_string().contains("invalid type"));
+
+    let content = r#"{ "name": "a", "version": "1", "scripts": {} }"#;
+    let error = from_str::<PackageJson>(content).unwrap_err();
+    assert!(error.to_string().contains("missing field `private`"));
+
+    let error = from_str::<u8>("[ 300 ]").unwrap_err();
+    assert_eq!(error.at(), 0);
+    let error = from_str::<Vec<u8>>("[ 300 ]").unwrap_err();
+    assert_eq!(error.at(), 2);
+
+    let error = from_str::<(u8, u8)>("[1, 2, 3]").unwrap_err();
+    assert_eq!(
+        error,
+        JSONDeserializeError::Parse(JSONParseError {
+            at: 7,
+            reason: JSONParseErrorReason::ExpectedBracket
+        })
+    );
+}
@@ -0,0 +1,102 @@
+use std::fmt::Write;
+
+use simple_json_parser::{JSONIterator, ParseOptions};
+
+/// Every event (or error) along with the path and span, as produced with and without the `simd` option
+fn events(on: &str, options: ParseOptions, simd: bool) -> Vec<String> {
+    let options = ParseOptions { simd, ..options };
+    let mut iterator = JSONIterator::with_options(on, options);
+    let mut events = Vec::new();
+    while let Some(event) = iterator.next_event() {
+        events.push(format!(
+            "{event:?} {:?} {:?} {:?}",
+            iterator.key_chain(),
+            iterator.key_spans(),
+            iterator.span()
+        ));
+    }
+    events
+}
+
+fn assert_same(on: &str, options: ParseOptions) {
+    assert_eq!(
+        events(on, options, false),
+        events(on, options, true),
+        "{on:?}"
+    );
+}
+
+fn document() -> String {
+    let long = "abcdefghijklmnopqrstuvwxyz0123456789".repeat(3);
+    let mut document = String::from("{\n");
+    for i in 0..40 {
+        let indent = " ".repeat(i % 37);
+        writeln!(
+            document,
+            "{indent}\"key {i} {long}\" :\t\r\n [ \"{long}\\n\\\"{long}\", \"ü{long}😀\", {i}, true, {{}} ],"
+        )
+        .unwrap();
+    }
+    document.push_str("    \"last\": null\n}");
+    document
+}
+
+#[test]
+fn same_events() {
+    let document = document();
+    for options in [
+        ParseOptions::default(),
+        ParseOptions::strict(),
+        ParseOptions::json5(),
+    ] {
+        assert_same(&document, options);
+    }
+
+    let json5 =
+        "{ unquoted: 'single quoted string that is longer than a block \\' \" ', hex: 0xFF }";
+    assert_same(json5, ParseOptions::json5());
+
+    let comments =
+        "[ // a comment that is longer than a block of thirty two bytes \"\n   1, /* \" */ 2 ]";
+    assert_same(comments, ParseOptions::default());
+}
+
+#[test]
+fn same_errors() {
+    let long = "x".repeat(100);
+    let cases = [
+        format!("[\"{long}\u{1}{long}\"]"),
+        format!("[\"{long}\\q\"]"),
+        format!("[\"{long}"),
+        format!("{{\"{long}\"                                      1}}"),
+        "[1,                                                   ]".to_owned(),
+        format!("[\"{long}\", \"{long}\" x, \"{long}\"]"),
+        format!("[{{\"a\": x{long} \"]\\\"\" [1, {{}}, '{long}]'] }}, 2, {long}]"),
+    ];
+    for case in &cases {
+        assert_same(case, ParseOptions::strict());
+        assert_same(case, ParseOptions::default());
+        assert_same(
+            case,
+            ParseOptions {
+                recover: true,
+                ..ParseOptions::strict()
+            },
+        );
+        assert_same(
+            case,
+            ParseOptions {
+                recover: true,
+                ..ParseOptions::json5()
+            },
+        );
+    }
+
+    for max in [0, 1, 15, 16, 17, 31, 32, 33, 99, 100, 101] {
+        let options = ParseOptions {
+            max_string_length: Some(max),
+            ..ParseOptions::default()
+        };
+        assert_same(&format!("{{\"{long}\": \"{long}\"}}"), options);
+    }
+}
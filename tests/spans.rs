@@ -0,0 +1,51 @@
+use simple_json_parser::{parse_with_spans, JSONEvent, JSONIterator, Span};
+
+#[test]
+fn value_and_key_spans() {
+    let content = r#"{ "name": "ezno", "list": [1, true, { "x": null }] }"#;
+
+    let mut found = Vec::new();
+    let result = parse_with_spans(content, |_keys, key_spans, _value, span| {
+        let keys = key_spans
+            .iter()
+            .map(|span| &content[span.start..span.end])
+            .collect::<Vec<_>>();
+        found.push((keys, &content[span.start..span.end]));
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(
+        found,
+        vec![
+            (vec![r#""name""#], r#""ezno""#),
+            (vec![r#""list""#, "1"], "1"),
+            (vec![r#""list""#, "true"], "true"),
+            (vec![r#""list""#, "{", r#""x""#], "null"),
+        ]
+    );
+}
+
+#[test]
+fn container_spans() {
+    let content = "[ {}, [ ] ]";
+    let mut iterator = JSONIterator::new(content);
+    let mut spans = Vec::new();
+    while let Some(event) = iterator.next_event() {
+        let event = event.unwrap();
+        assert!(!matches!(event, JSONEvent::Value(_)));
+        spans.push(iterator.span());
+    }
+
+    let at = |start| Span {
+        start,
+        end: start + 1,
+    };
+    assert_eq!(spans, vec![at(0), at(2), at(3), at(6), at(8), at(10)]);
+}
+
+#[test]
+fn trailing_number_span() {
+    let mut iterator = JSONIterator::new("  -12.5");
+    assert!(iterator.next_value().unwrap().is_ok());
+    assert_eq!(iterator.span(), Span { start: 2, end: 7 });
+}
@@ -0,0 +1,160 @@
+use simple_json_parser::{
+    parse, parse_with_recovery, JSONKey, JSONParseError, JSONParseErrorReason, JSONStreamParser,
+    ParseOptions, RootJSONValue,
+};
+
+type Item = (Vec<String>, String);
+
+fn to_item(keys: &[JSONKey], value: &RootJSONValue) -> Item {
+    let keys = keys
+        .iter()
+        .map(|key| match key {
+            JSONKey::Slice(key) => (*key).to_owned(),
+            JSONKey::Index(index) => index.to_string(),
+        })
+        .collect();
+    (keys, format!("{value:?}"))
+}
+
+fn in_chunks(content: &[u8], size: usize) -> Result<Vec<Item>, JSONParseError> {
+    let mut items = Vec::new();
+    let mut parser = JSONStreamParser::new(|keys, value| {
+        items.push(to_item(keys, &value));
+        false
+    });
+    for chunk in content.chunks(size) {
+        parser.feed(chunk)?;
+    }
+    parser.finish()?;
+    Ok(items)
+}
+
+#[test]
+fn same_values_for_any_chunk_size() {
+    let content = r#"{
+        "name": "simple-json-parser", // comment
+        "escaped \"key\"": "line\nbreak 😀",
+        "non ascii": "héllo wörld ✓ 😀",
+        "numbers": [0, -12.5e+3, 1234567890, 1e10],
+        "literals": [true, false, null],
+        /* block
+           comment */
+        "nested": { "empty": {}, "array": [[], [{}]] }
+    }"#;
+
+    let mut expected = Vec::new();
+    parse(content, |keys, value| expected.push(to_item(keys, &value))).unwrap();
+
+    for size in 1..=content.len() {
+        assert_eq!(
+            in_chunks(content.as_bytes(), size).unwrap(),
+            expected,
+            "chunk size {size}"
+        );
+    }
+}
+
+#[test]
+fn number_at_root_ends_on_finish() {
+    for size in 1..=4 {
+        assert_eq!(
+            in_chunks(b"1234", size).unwrap(),
+            vec![(vec![], "Number(\"1234\")".to_owned())]
+        );
+    }
+}
+
+#[test]
+fn error_offsets_are_from_the_start() {
+    let content = br#"{ "a": 1, "b": [true, tru] }"#;
+    for size in 1..=content.len() {
+        assert_eq!(
+            in_chunks(content, size),
+            Err(JSONParseError {
+                at: 25,
+                reason: JSONParseErrorReason::ExpectedTrueFalseNull
+            })
+        );
+    }
+}
+
+#[test]
+fn recovery_continues_through_the_chunk() {
+    let content = "[tru, 1, x, 2, 3]";
+    let mut expected = Vec::new();
+    let errors = parse_with_recovery(content, ParseOptions::default(), |keys, value| {
+        expected.push(to_item(keys, &value));
+    });
+    assert_eq!(errors.len(), 2);
+
+    let options = ParseOptions {
+        recover: true,
+        ..ParseOptions::default()
+    };
+    for size in [1, 4, content.len()] {
+        let mut items = Vec::new();
+        let mut first = None;
+        let mut parser = JSONStreamParser::with_options(options, |keys, value| {
+            items.push(to_item(keys, &value));
+            false
+        });
+        for chunk in content.as_bytes().chunks(size) {
+            first = first.or(parser.feed(chunk).err());
+        }
+        first = first.or(parser.finish().err());
+        assert_eq!(items, expected, "chunk size {size}");
+        assert_eq!(first, Some(errors[0].clone()), "chunk size {size}");
+    }
+}
+
+#[test]
+fn incomplete_input() {
+    assert_eq!(
+        in_chunks(br#"{ "a": "b"#, 3),
+        Err(JSONParseError {
+            at: 9,
+            reason: JSONParseErrorReason::ExpectedQuote
+        })
+    );
+    assert_eq!(
+        in_chunks(b"[1, 2", 1),
+        Err(JSONParseError {
+            at: 5,
+            reason: JSONParseErrorReason::ExpectedBracket
+        })
+    );
+}
+
+#[test]
+fn invalid_utf8() {
+    assert_eq!(
+        in_chunks(b"[\"a\", \"\xff\"]", 2),
+        Err(JSONParseError {
+            at: 7,
+            reason: JSONParseErrorReason::InvalidUTF8
+        })
+    );
+    // Truncated in the middle of a character
+    assert_eq!(
+        in_chunks(b"\"\xe2\x9c", 1),
+        Err(JSONParseError {
+            at: 1,
+            reason: JSONParseErrorReason::InvalidUTF8
+        })
+    );
+}
+
+#[test]
+fn exit_signal() {
+    let mut count = 0;
+    let mut parser = JSONStreamParser::with_options(ParseOptions::strict(), |_keys, _value| {
+        count += 1;
+        count == 2
+    });
+    parser.feed(b"[1, 2, 3").unwrap();
+    assert!(parser.is_finished());
+    // Ignored after exiting
+    parser.feed(b"invalid").unwrap();
+    parser.finish().unwrap();
+    assert_eq!(count, 2);
+}
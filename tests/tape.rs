@@ -0,0 +1,114 @@
+use simple_json_parser::{JSONParseErrorReason, RootJSONValue, Tape, TapeNode};
+
+#[test]
+fn layout() {
+    let tape = Tape::parse(r#"{ "a": [1, {}], "b": "x" }"#).unwrap();
+    assert_eq!(
+        tape.nodes(),
+        [
+            TapeNode::Object { end: 7, len: 2 },
+            TapeNode::Key("a"),
+            TapeNode::Array { end: 5, len: 2 },
+            TapeNode::Number("1"),
+            TapeNode::Object { end: 5, len: 0 },
+            TapeNode::Key("b"),
+            TapeNode::String("x"),
+        ]
+    );
+}
+
+#[test]
+fn navigation() {
+    let content = r#"{
+        "name": "simple-json-parser",
+        "versions": [
+            { "number": "0.0.1", "yanked": true, "deps": [[], [[]], {}] },
+            { "number": "0.0.2", "yanked": false, "deps": [] }
+        ],
+        "downloads": 1200,
+        "owner": null
+    }"#;
+    let tape = Tape::parse(content).unwrap();
+    let root = tape.root();
+
+    assert_eq!(root.len(), Some(4));
+    assert_eq!(
+        root.get("name").and_then(|name| name.as_str()).as_deref(),
+        Some("simple-json-parser")
+    );
+    assert_eq!(
+        root.get("downloads")
+            .and_then(|downloads| downloads.as_u64()),
+        Some(1200)
+    );
+    assert!(root.get("owner").unwrap().is_null());
+    assert!(root.get("missing").is_none());
+
+    let versions = root.get("versions").unwrap();
+    assert_eq!(versions.len(), Some(2));
+    let second = versions.index(1).unwrap();
+    assert_eq!(
+        second.get("yanked").and_then(|yanked| yanked.as_bool()),
+        Some(false)
+    );
+    assert_eq!(second.get("deps").unwrap().is_empty(), Some(true));
+    assert!(versions.index(2).is_none());
+
+    let numbers: Vec<_> = versions
+        .items()
+        .unwrap()
+        .map(|version| version.get("number").unwrap().value())
+        .collect();
+    assert_eq!(
+        numbers,
+        [
+            Some(RootJSONValue::String("0.0.1")),
+            Some(RootJSONValue::String("0.0.2"))
+        ]
+    );
+
+    let keys: Vec<_> = root.members().unwrap().map(|(key, _)| key).collect();
+    assert_eq!(keys, ["name", "versions", "downloads", "owner"]);
+
+    // Not containers
+    assert!(root.index(0).is_none());
+    assert!(versions.get("number").is_none());
+    assert!(root.get("downloads").unwrap().members().is_none());
+    assert!(root.get("name").unwrap().len().is_none());
+}
+
+#[test]
+fn keys() {
+    let tape = Tape::parse(r#"{ "a": 1, "b": 2, "a": 3 }"#).unwrap();
+    let root = tape.root();
+    assert_eq!(root.get("b").and_then(|b| b.as_i64()), Some(2));
+    // Last of duplicates, like `Value::get`
+    assert_eq!(root.get("a").and_then(|a| a.as_i64()), Some(3));
+    assert_eq!(root.len(), Some(3));
+
+    let tape = Tape::parse(r#"{ "\u0061": "\u0062" }"#).unwrap();
+    let a = tape.root().get("a").unwrap();
+    assert_eq!(a.node(), TapeNode::String("\\u0062"));
+    assert_eq!(a.as_str().as_deref(), Some("b"));
+
+    let tape = Tape::parse(r#"{ "a": 1, "\u0061": 2, "b\"": 3 }"#).unwrap();
+    let root = tape.root();
+    assert_eq!(root.get("a").and_then(|a| a.as_i64()), Some(2));
+    assert_eq!(root.get("b\"").and_then(|b| b.as_i64()), Some(3));
+    assert!(root.get("b").is_none());
+}
+
+#[test]
+fn roots() {
+    let tape = Tape::parse(" 4.5 ").unwrap();
+    assert_eq!(tape.nodes(), [TapeNode::Number("4.5")]);
+    assert_eq!(tape.root().as_f64(), Some(4.5));
+
+    let tape = Tape::parse("[] // comment").unwrap();
+    assert_eq!(tape.nodes(), [TapeNode::Array { end: 1, len: 0 }]);
+
+    let error = Tape::parse("").unwrap_err();
+    assert_eq!(error.reason, JSONParseErrorReason::ExpectedValue);
+    let error = Tape::parse("[1, [2]").unwrap_err();
+    assert_eq!(error.reason, JSONParseErrorReason::ExpectedBracket);
+}
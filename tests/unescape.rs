@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+
+use simple_json_parser::{parse, unescape, JSONParseError, JSONParseErrorReason};
+
+#[test]
+fn borrows_without_escapes() {
+    assert!(matches!(unescape("plain é"), Cow::Borrowed("plain é")));
+}
+
+#[test]
+fn decodes_escapes() {
+    let content = r#"{ "a\"b": "line\nbreak é 😀 \\ \/" }"#;
+
+    let mut found = Vec::new();
+    let result = parse(content, |keys, value| {
+        found.push((
+            keys[0].as_str().unwrap().into_owned(),
+            value.as_str().unwrap().into_owned(),
+        ));
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(
+        found,
+        vec![("a\"b".to_owned(), "line\nbreak é 😀 \\ /".to_owned())]
+    );
+}
+
+#[test]
+fn invalid_escapes() {
+    for (content, at) in [
+        (r#""\x""#, 2),
+        (r#""\u00g0""#, 5),
+        (r#""\ud83d""#, 7),
+        (r#""\ud83d\n""#, 8),
+        (r#""\ude00""#, 6),
+        (r#"{ "\q": 1 }"#, 4),
+    ] {
+        let result = parse(content, |_, _| {});
+        assert_eq!(
+            result,
+            Err(JSONParseError {
+                at,
+                reason: JSONParseErrorReason::InvalidEscape
+            }),
+            "{content}"
+        );
+    }
+}
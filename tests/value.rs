@@ -0,0 +1,132 @@
+use std::borrow::Cow;
+
+use simple_json_parser::{JSONParseErrorReason, Object, ParseOptions, Value};
+
+#[test]
+fn structure() {
+    let content = r#"{
+        "name": "simple-json-parser",
+        "keywords": ["json", "parser"],
+        "nested": { "a": 1, "b": [true, false, null] },
+        "empty_object": {},
+        "empty_array": []
+    }"#;
+
+    let value = Value::parse(content).unwrap();
+
+    assert_eq!(
+        value,
+        Value::Object(Object(vec![
+            ("name".into(), Value::String("simple-json-parser".into())),
+            (
+                "keywords".into(),
+                Value::Array(vec![
+                    Value::String("json".into()),
+                    Value::String("parser".into())
+                ])
+            ),
+            (
+                "nested".into(),
+                Value::Object(Object(vec![
+                    ("a".into(), Value::Number("1")),
+                    (
+                        "b".into(),
+                        Value::Array(vec![
+                            Value::Boolean(true),
+                            Value::Boolean(false),
+                            Value::Null
+                        ])
+                    ),
+                ]))
+            ),
+            ("empty_object".into(), Value::Object(Object::default())),
+            ("empty_array".into(), Value::Array(Vec::new())),
+        ]))
+    );
+
+    let keys: Vec<_> = value
+        .as_object()
+        .unwrap()
+        .iter()
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(
+        keys,
+        ["name", "keywords", "nested", "empty_object", "empty_array"]
+    );
+    assert_eq!(
+        value
+            .get("keywords")
+            .and_then(|keywords| keywords.get_index(1)),
+        Some(&Value::String("parser".into()))
+    );
+    assert_eq!(
+        value
+            .get("nested")
+            .and_then(|nested| nested.get("a"))
+            .and_then(Value::as_i64),
+        Some(1)
+    );
+    assert!(value
+        .get("empty_array")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .is_empty());
+    assert!(value
+        .get("empty_object")
+        .unwrap()
+        .as_object()
+        .unwrap()
+        .is_empty());
+    assert_eq!(value.get("missing"), None);
+}
+
+#[test]
+fn strings_borrow_unless_escaped() {
+    let value = Value::parse(r#"{ "plain": "abc", "\u0065scaped": "a\nb" }"#).unwrap();
+    let Value::Object(Object(members)) = value else {
+        panic!()
+    };
+
+    assert!(matches!(members[0].0, Cow::Borrowed("plain")));
+    assert!(matches!(members[0].1, Value::String(Cow::Borrowed("abc"))));
+    assert!(matches!(members[1].0, Cow::Owned(ref key) if key == "escaped"));
+    assert!(matches!(members[1].1, Value::String(Cow::Owned(ref value)) if value == "a\nb"));
+}
+
+#[test]
+fn duplicate_keys() {
+    let value = Value::parse(r#"{ "a": 1, "b": 2, "a": 3 }"#).unwrap();
+    assert_eq!(value.as_object().unwrap().len(), 3);
+    assert_eq!(value.get("a"), Some(&Value::Number("3")));
+}
+
+#[test]
+fn roots_and_comments() {
+    assert_eq!(Value::parse("  42 ").unwrap().as_u64(), Some(42));
+    assert_eq!(Value::parse(r#""é""#).unwrap().as_str(), Some("\u{e9}"));
+    assert_eq!(
+        Value::parse("[1, /* two */ 2, // three\n]").unwrap(),
+        Value::Array(vec![Value::Number("1"), Value::Number("2")])
+    );
+    assert_eq!(
+        Value::parse("[[[]]]").unwrap(),
+        Value::Array(vec![Value::Array(vec![Value::Array(Vec::new())])])
+    );
+}
+
+#[test]
+fn errors() {
+    let error = Value::parse(r#"{ "a": [1, 2 }"#).unwrap_err();
+    assert_eq!(error.reason, JSONParseErrorReason::ExpectedEndOfValue);
+
+    let error = Value::parse("  ").unwrap_err();
+    assert_eq!(
+        (error.at, error.reason),
+        (2, JSONParseErrorReason::ExpectedValue)
+    );
+
+    let error = Value::parse_with_options("[1,]", ParseOptions::strict()).unwrap_err();
+    assert_eq!(error.reason, JSONParseErrorReason::TrailingComma);
+}
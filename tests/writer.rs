@@ -0,0 +1,216 @@
+use simple_json_parser::{
+    format, format_with_options, minify, CommentKind, JSONParseErrorReason, JSONWriteError,
+    JSONWriter, ParseOptions, RootJSONValue, WriteOptions,
+};
+
+#[test]
+fn writer() {
+    let mut writer = JSONWriter::new(String::new());
+    writer.start_object().unwrap();
+    writer.key("name").unwrap();
+    writer.string("simple \"json\" parser").unwrap();
+    writer.key("tags").unwrap();
+    writer.start_array().unwrap();
+    writer.value(RootJSONValue::Number("1.5")).unwrap();
+    writer.value(RootJSONValue::True).unwrap();
+    writer.value(RootJSONValue::Null).unwrap();
+    writer.start_object().unwrap();
+    writer.end_object().unwrap();
+    writer.end_array().unwrap();
+    writer.end_object().unwrap();
+    writer.finish().unwrap();
+    assert_eq!(
+        writer.into_inner(),
+        r#"{"name":"simple \"json\" parser","tags":[1.5,true,null,{}]}"#
+    );
+}
+
+#[test]
+fn escaping() {
+    let mut writer = JSONWriter::new(String::new());
+    writer
+        .string("\\ \n\r\t\u{8}\u{c}\u{1} é / \u{7f}")
+        .unwrap();
+    assert_eq!(
+        writer.into_inner(),
+        "\"\\\\ \\n\\r\\t\\b\\f\\u0001 é / \u{7f}\""
+    );
+
+    // Raw strings from the parser are re-escaped
+    let mut writer = JSONWriter::new(String::new());
+    writer.value(RootJSONValue::String(r"A\/")).unwrap();
+    assert_eq!(writer.into_inner(), r#""A/""#);
+}
+
+#[test]
+fn pretty() {
+    let content = r#"{"a":[1,2,[]],"b":{"c":null},"d":{}}"#;
+    assert_eq!(
+        format(content, WriteOptions::pretty()).unwrap(),
+        r#"{
+  "a": [
+    1,
+    2,
+    []
+  ],
+  "b": {
+    "c": null
+  },
+  "d": {}
+}"#
+    );
+
+    let options = WriteOptions {
+        indent: Some("\t"),
+        comments: false,
+    };
+    assert_eq!(format("[1]", options).unwrap(), "[\n\t1\n]");
+    assert_eq!(format(" 42 ", options).unwrap(), "42");
+}
+
+#[test]
+fn comments() {
+    let content = "// header
+{
+    // before a
+    \"a\": 1, /* after a */
+    \"b\": [
+        2 // last
+    ]
+}
+# footer";
+    let formatted = format(content, WriteOptions::pretty()).unwrap();
+    assert_eq!(
+        formatted,
+        "// header
+{
+  // before a
+  \"a\": 1,
+  /* after a */
+  \"b\": [
+    2
+    // last
+  ]
+}
+# footer"
+    );
+    // Formatting is stable
+    assert_eq!(
+        format(&formatted, WriteOptions::pretty()).unwrap(),
+        formatted
+    );
+
+    // Line comments end with a new line when compact
+    let options = WriteOptions {
+        indent: None,
+        comments: true,
+    };
+    let compact = format(content, options).unwrap();
+    assert_eq!(
+        compact,
+        "// header\n{// before a\n\"a\":1,/* after a */\"b\":[2// last\n]}# footer\n"
+    );
+    assert_eq!(minify(&compact).unwrap(), minify(content).unwrap());
+
+    // Between a key and its value
+    let mut writer = JSONWriter::with_options(String::new(), WriteOptions::pretty());
+    writer.start_object().unwrap();
+    writer.key("a").unwrap();
+    writer.comment(" one ", CommentKind::Block);
+    writer.value(RootJSONValue::Number("1")).unwrap();
+    writer.end_object().unwrap();
+    assert_eq!(writer.into_inner(), "{\n  \"a\": /* one */ 1\n}");
+}
+
+#[test]
+fn minify_and_round_trip() {
+    let content = r#"{
+        "name": "simple-json-parser", // name
+        "values": [1e3, -0.5, "é\n", true, false, null, [], {},],
+    }"#;
+    let minified = minify(content).unwrap();
+    assert_eq!(
+        minified,
+        r#"{"name":"simple-json-parser","values":[1e3,-0.5,"é\n",true,false,null,[],{}]}"#
+    );
+    // Output is strict JSON
+    assert_eq!(
+        format_with_options(&minified, ParseOptions::strict(), WriteOptions::compact()).unwrap(),
+        minified
+    );
+    assert_eq!(
+        minify(&format(content, WriteOptions::pretty()).unwrap()).unwrap(),
+        minified
+    );
+    let options = WriteOptions {
+        comments: false,
+        ..WriteOptions::pretty()
+    };
+    let expected: serde_json::Value = serde_json::from_str(&minified).unwrap();
+    let pretty: serde_json::Value =
+        serde_json::from_str(&format(content, options).unwrap()).unwrap();
+    assert_eq!(pretty, expected);
+}
+
+#[test]
+fn errors() {
+    let error = minify("[1, 2").unwrap_err();
+    assert_eq!(error.reason, JSONParseErrorReason::ExpectedBracket);
+    let error = minify("").unwrap_err();
+    assert_eq!(
+        (error.at, error.reason),
+        (0, JSONParseErrorReason::ExpectedValue)
+    );
+}
+
+#[test]
+fn json5_is_written_as_json() {
+    let content =
+        "[0xFF, -0x10, 0x10000000000000000000000000000000, +1, .5, -5., 5.e3, 'a\\'', \"é\\x41\"]";
+    let json = format_with_options(content, ParseOptions::json5(), WriteOptions::compact());
+    assert_eq!(
+        json.unwrap(),
+        r#"[255,-16,21267647932558653966460912964485513216,1,0.5,-5,5e3,"a'","éA"]"#
+    );
+
+    for content in ["Infinity", "[1, -Infinity]", "{ a: NaN }"] {
+        let error = format_with_options(content, ParseOptions::json5(), WriteOptions::compact());
+        assert_eq!(
+            error.map_err(|error| (error.at, error.reason)),
+            Err((
+                content.find(['-', 'I', 'N']).unwrap(),
+                JSONParseErrorReason::InvalidNumber
+            )),
+            "{content}"
+        );
+    }
+
+    let mut writer = JSONWriter::new(String::new());
+    assert_eq!(
+        writer.value(RootJSONValue::Number("NaN")),
+        Err(JSONWriteError::InvalidNumber)
+    );
+    assert_eq!(writer.into_inner(), "");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io() {
+    use simple_json_parser::IoWriter;
+
+    let mut writer = JSONWriter::new(IoWriter::new(Vec::new()));
+    writer.start_array().unwrap();
+    writer.string("a").unwrap();
+    writer.end_array().unwrap();
+    assert_eq!(writer.into_inner().into_inner().unwrap(), br#"["a"]"#);
+
+    let mut buffer = [0u8; 4];
+    let mut writer = JSONWriter::new(IoWriter::new(&mut buffer[..]));
+    writer.string("too long").unwrap_err();
+    assert!(matches!(
+        writer.value(RootJSONValue::Null),
+        Err(JSONWriteError::Fmt(_))
+    ));
+    let error = writer.into_inner().into_inner().unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+}
@@ -0,0 +1,196 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{
+    unescape, JSONDecimal, JSONEvent, JSONIterator, JSONKey, JSONParseError, ParseOptions,
+    RootJSONValue,
+};
+
+/// A whole JSON document. Strings and keys borrow from the input unless they contain escapes
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    Object(Object<'a>),
+    Array(Vec<Value<'a>>),
+    String(Cow<'a, str>),
+    /// The text of the number, see [`Value::as_f64`] etc
+    Number(&'a str),
+    Boolean(bool),
+    Null,
+}
+
+/// Members in the order they appear. Duplicate keys are kept
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Object<'a>(pub Vec<(Cow<'a, str>, Value<'a>)>);
+
+impl<'a> Object<'a> {
+    /// The value of the last member named `key`
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value<'a>> {
+        self.0
+            .iter()
+            .rev()
+            .find_map(|(name, value)| (name == key).then_some(value))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value<'a>)> {
+        self.0.iter().map(|(key, value)| (key.as_ref(), value))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a> Value<'a> {
+    /// # Errors
+    /// Returns an error if `on` is not valid JSON or is empty
+    pub fn parse(on: &'a str) -> Result<Self, JSONParseError> {
+        Self::parse_with_options(on, ParseOptions::default())
+    }
+
+    /// [`Value::parse`] with a choice of which extensions to accept. Stops at the first error, even
+    /// with [`ParseOptions::recover`]
+    ///
+    /// # Errors
+    /// Returns an error if `on` is invalid under `options` or is empty
+    pub fn parse_with_options(on: &'a str, options: ParseOptions) -> Result<Self, JSONParseError> {
+        let mut iterator = JSONIterator::with_options(on, options);
+        // Containers that have been started but not ended, with their key if in an object
+        let mut stack: Vec<(Option<Cow<'a, str>>, Value<'a>)> = Vec::new();
+        let mut root = None;
+        while let Some(event) = iterator.next_event() {
+            let value = match event? {
+                JSONEvent::ObjectStart => Value::Object(Object::default()),
+                JSONEvent::ArrayStart => Value::Array(Vec::new()),
+                JSONEvent::ObjectEnd | JSONEvent::ArrayEnd => {
+                    if let Some((key, value)) = stack.pop() {
+                        match stack.last_mut() {
+                            Some((_, parent)) => parent.insert(key, value),
+                            None => root = Some(value),
+                        }
+                    }
+                    continue;
+                }
+                JSONEvent::Value(value) => Value::from(value),
+                JSONEvent::Comment(_) => continue,
+            };
+            let key = match (stack.last(), iterator.key_chain().last()) {
+                (Some((_, Value::Object(_))), Some(JSONKey::Slice(key))) => Some(unescape(key)),
+                _ => None,
+            };
+            match (value, stack.last_mut()) {
+                (value @ (Value::Object(_) | Value::Array(_)), _) => stack.push((key, value)),
+                (value, Some((_, parent))) => parent.insert(key, value),
+                (value, None) => root = Some(value),
+            }
+        }
+        iterator.expect_value()?;
+        Ok(root.unwrap_or(Value::Null))
+    }
+
+    /// Adds to an object or array
+    fn insert(&mut self, key: Option<Cow<'a, str>>, value: Value<'a>) {
+        match self {
+            Value::Object(object) => object.0.push((key.unwrap_or_default(), value)),
+            Value::Array(items) => items.push(value),
+            _ => unreachable!("only containers are on the stack"),
+        }
+    }
+
+    /// The value of the last member named `key`, if an object
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value<'a>> {
+        self.as_object()?.get(key)
+    }
+
+    /// The item at `index`, if an array
+    #[must_use]
+    pub fn get_index(&self, index: usize) -> Option<&Value<'a>> {
+        self.as_array()?.get(index)
+    }
+
+    #[must_use]
+    pub fn as_object(&self) -> Option<&Object<'a>> {
+        match self {
+            Value::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_array(&self) -> Option<&[Value<'a>]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// See [`RootJSONValue::as_i64`]
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_number()?.as_i64()
+    }
+
+    /// See [`RootJSONValue::as_u64`]
+    #[must_use]
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_number()?.as_u64()
+    }
+
+    /// See [`RootJSONValue::as_f64`]
+    #[must_use]
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_number()?.as_f64()
+    }
+
+    /// See [`RootJSONValue::as_decimal`]
+    #[must_use]
+    pub fn as_decimal(&self) -> Option<JSONDecimal<'a>> {
+        self.as_number()?.as_decimal()
+    }
+
+    fn as_number(&self) -> Option<RootJSONValue<'a>> {
+        match self {
+            Value::Number(number) => Some(RootJSONValue::Number(number)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> From<RootJSONValue<'a>> for Value<'a> {
+    fn from(value: RootJSONValue<'a>) -> Self {
+        match value {
+            RootJSONValue::String(value) => Value::String(unescape(value)),
+            RootJSONValue::Number(number) => Value::Number(number),
+            RootJSONValue::True => Value::Boolean(true),
+            RootJSONValue::False => Value::Boolean(false),
+            RootJSONValue::Null => Value::Null,
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use crate::{
+    number::to_json, unescape, CommentKind, JSONEvent, JSONIterator, JSONKey, JSONParseError,
+    JSONParseErrorReason, ParseOptions, RootJSONValue,
+};
+
+/// How [`JSONWriter`] lays out its output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Written once per level of nesting at the start of each member and item, which go on their
+    /// own lines. `None` writes everything on one line without spaces
+    pub indent: Option<&'static str>,
+    /// Write comments from [`JSONWriter::comment`], otherwise they are dropped
+    pub comments: bool,
+}
+
+impl WriteOptions {
+    /// Two space indentation, keeping comments
+    #[must_use]
+    pub fn pretty() -> Self {
+        Self {
+            indent: Some("  "),
+            comments: true,
+        }
+    }
+
+    /// No whitespace or comments
+    #[must_use]
+    pub fn compact() -> Self {
+        Self {
+            indent: None,
+            comments: false,
+        }
+    }
+}
+
+/// Error from [`JSONWriter::value`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JSONWriteError {
+    /// `Infinity` or `NaN`, which JSON cannot represent. Nothing is written
+    InvalidNumber,
+    /// Writing failed
+    Fmt(fmt::Error),
+}
+
+impl core::error::Error for JSONWriteError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            JSONWriteError::InvalidNumber => None,
+            JSONWriteError::Fmt(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for JSONWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JSONWriteError::InvalidNumber => {
+                f.write_str("JSONWriteError: Infinity and NaN cannot be written as JSON")
+            }
+            JSONWriteError::Fmt(error) => f.write_fmt(format_args!("JSONWriteError: {error}")),
+        }
+    }
+}
+
+impl From<fmt::Error> for JSONWriteError {
+    fn from(error: fmt::Error) -> Self {
+        JSONWriteError::Fmt(error)
+    }
+}
+
+/// An object or array that has been started but not ended
+struct Level {
+    object: bool,
+    empty: bool,
+}
+
+/// Writes JSON piece by piece to a [`fmt::Write`], such as a [`String`]. With the `std` feature, use
+/// `IoWriter` for `std::io::Write`s. Adds commas, colons and indentation, but does not check that pieces are in
+/// a valid order, for example that members of objects have keys
+pub struct JSONWriter<W> {
+    out: W,
+    options: WriteOptions,
+    levels: Vec<Level>,
+    /// A key has been written and its value has not
+    after_key: bool,
+    /// Written before the next key, value or end, so they go after the comma
+    comments: Vec<(String, CommentKind)>,
+}
+
+impl<W: Write> JSONWriter<W> {
+    /// Compact output, see [`WriteOptions::compact`]
+    pub fn new(out: W) -> Self {
+        Self::with_options(out, WriteOptions::compact())
+    }
+
+    pub fn with_options(out: W, options: WriteOptions) -> Self {
+        Self {
+            out,
+            options,
+            levels: Vec::new(),
+            after_key: false,
+            comments: Vec::new(),
+        }
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn start_object(&mut self) -> fmt::Result {
+        self.before_value()?;
+        self.levels.push(Level {
+            object: true,
+            empty: true,
+        });
+        self.out.write_char('{')
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn end_object(&mut self) -> fmt::Result {
+        self.end('}')
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn start_array(&mut self) -> fmt::Result {
+        self.before_value()?;
+        self.levels.push(Level {
+            object: false,
+            empty: true,
+        });
+        self.out.write_char('[')
+    }
+
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn end_array(&mut self) -> fmt::Result {
+        self.end(']')
+    }
+
+    /// Escapes `key`, which should not already be escaped
+    ///
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn key(&mut self, key: &str) -> fmt::Result {
+        self.before_item()?;
+        self.write_string(key)?;
+        self.out.write_char(':')?;
+        if self.options.indent.is_some() {
+            self.out.write_char(' ')?;
+        }
+        self.after_key = true;
+        Ok(())
+    }
+
+    /// Escapes `value`, which should not already be escaped
+    ///
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn string(&mut self, value: &str) -> fmt::Result {
+        self.before_value()?;
+        self.write_string(value)
+    }
+
+    /// Strings are the raw contents between the quotes, as from the parser, and are written with
+    /// escapes decoded then re-escaped. JSON5 numbers are written as JSON, with hexadecimal numbers
+    /// in decimal
+    ///
+    /// # Errors
+    /// Returns [`JSONWriteError::Fmt`] if writing fails and [`JSONWriteError::InvalidNumber`] for
+    /// `Infinity` and `NaN`, which JSON cannot represent
+    #[allow(clippy::needless_pass_by_value)] // Matches how values are passed to callbacks
+    pub fn value(&mut self, value: RootJSONValue<'_>) -> Result<(), JSONWriteError> {
+        let literal = match value {
+            RootJSONValue::String(value) => return Ok(self.string(&unescape(value))?),
+            RootJSONValue::Number(value) => to_json(value).ok_or(JSONWriteError::InvalidNumber)?,
+            RootJSONValue::True => "true".into(),
+            RootJSONValue::False => "false".into(),
+            RootJSONValue::Null => "null".into(),
+        };
+        self.before_value()?;
+        Ok(self.out.write_str(&literal)?)
+    }
+
+    /// Written before whatever comes next, after the comma. `text` is without delimiters and
+    /// should not contain a new line for line comments or `*/` for block comments. Dropped
+    /// without [`WriteOptions::comments`]
+    pub fn comment(&mut self, text: &str, kind: CommentKind) {
+        if self.options.comments {
+            self.comments.push((text.to_owned(), kind));
+        }
+    }
+
+    /// Writes comments after the root value
+    ///
+    /// # Errors
+    /// Returns an error if writing fails
+    pub fn finish(&mut self) -> fmt::Result {
+        for (text, kind) in core::mem::take(&mut self.comments) {
+            if self.options.indent.is_some() {
+                self.new_line()?;
+            }
+            self.write_comment(&text, kind)?;
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn in_object(&self) -> bool {
+        self.levels.last().is_some_and(|level| level.object)
+    }
+
+    fn before_value(&mut self) -> fmt::Result {
+        if core::mem::take(&mut self.after_key) {
+            // Comments between the key and value go on the same line where possible
+            for (text, kind) in core::mem::take(&mut self.comments) {
+                self.write_comment(&text, kind)?;
+                if let CommentKind::Block = kind {
+                    if self.options.indent.is_some() {
+                        self.out.write_char(' ')?;
+                    }
+                } else {
+                    self.new_line()?;
+                }
+            }
+            Ok(())
+        } else {
+            self.before_item()
+        }
+    }
+
+    /// Comma, comments and indentation before a member or item
+    fn before_item(&mut self) -> fmt::Result {
+        if let Some(level) = self.levels.last_mut() {
+            if !core::mem::replace(&mut level.empty, false) {
+                self.out.write_char(',')?;
+            }
+            self.new_line()?;
+        }
+        for (text, kind) in core::mem::take(&mut self.comments) {
+            self.write_comment(&text, kind)?;
+            self.new_line()?;
+        }
+        Ok(())
+    }
+
+    fn end(&mut self, bracket: char) -> fmt::Result {
+        let comments = core::mem::take(&mut self.comments);
+        // Not indented when there are no members, items or comments
+        let indented = !comments.is_empty() || self.levels.last().is_some_and(|level| !level.empty);
+        for (text, kind) in comments {
+            self.new_line()?;
+            self.write_comment(&text, kind)?;
+        }
+        self.levels.pop();
+        if indented {
+            self.new_line()?;
+        }
+        self.out.write_char(bracket)
+    }
+
+    /// New line and indentation, if indenting
+    fn new_line(&mut self) -> fmt::Result {
+        if let Some(indent) = self.options.indent {
+            self.out.write_char('\n')?;
+            for _ in 0..self.levels.len() {
+                self.out.write_str(indent)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Line comments end with a new line without indentation, so that they do not comment out
+    /// what comes after
+    fn write_comment(&mut self, text: &str, kind: CommentKind) -> fmt::Result {
+        match kind {
+            CommentKind::Line | CommentKind::Hash => {
+                self.out.write_str(if let CommentKind::Hash = kind {
+                    "#"
+                } else {
+                    "//"
+                })?;
+                self.out.write_str(text)?;
+                if self.options.indent.is_none() {
+                    self.out.write_char('\n')?;
+                }
+                Ok(())
+            }
+            CommentKind::Block => {
+                self.out.write_str("/*")?;
+                self.out.write_str(text)?;
+                self.out.write_str("*/")
+            }
+        }
+    }
+
+    fn write_string(&mut self, value: &str) -> fmt::Result {
+        self.out.write_char('"')?;
+        let mut start = 0;
+        for (idx, chr) in value.char_indices() {
+            let escaped = match chr {
+                '"' => "\\\"",
+                '\\' => "\\\\",
+                '\n' => "\\n",
+                '\r' => "\\r",
+                '\t' => "\\t",
+                '\u{8}' => "\\b",
+                '\u{c}' => "\\f",
+                '\0'..='\u{1f}' => "",
+                _ => continue,
+            };
+            self.out.write_str(&value[start..idx])?;
+            if escaped.is_empty() {
+                self.out
+                    .write_fmt(format_args!("\\u{:04x}", u32::from(chr)))?;
+            } else {
+                self.out.write_str(escaped)?;
+            }
+            start = idx + chr.len_utf8();
+        }
+        self.out.write_str(&value[start..])?;
+        self.out.write_char('"')
+    }
+}
+
+/// [`fmt::Write`] for a [`std::io::Write`], for [`JSONWriter`]. Keeps the first error, which
+/// [`IoWriter::into_inner`] returns. Wrap unbuffered writers such as files in a
+/// [`std::io::BufWriter`]
+#[cfg(feature = "std")]
+pub struct IoWriter<W> {
+    inner: W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, error: None }
+    }
+
+    /// # Errors
+    /// Returns the error from writing, if it failed
+    pub fn into_inner(self) -> std::io::Result<W> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.error.is_some() {
+            return Err(fmt::Error);
+        }
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Re-writes `on` with the layout from `options`. Keeps comments with [`WriteOptions::comments`]
+///
+/// # Errors
+/// Returns an error if `on` is not valid JSON or is empty
+pub fn format(on: &str, options: WriteOptions) -> Result<String, JSONParseError> {
+    format_with_options(on, ParseOptions::default(), options)
+}
+
+/// Re-writes `on` without whitespace or comments
+///
+/// # Errors
+/// Returns an error if `on` is not valid JSON or is empty
+pub fn minify(on: &str) -> Result<String, JSONParseError> {
+    format(on, WriteOptions::compact())
+}
+
+/// [`format()`] with a choice of which extensions to accept. The output is always JSON, JSON5 strings
+/// are re-escaped and numbers written as with [`JSONWriter::value`]
+///
+/// # Errors
+/// Returns an error if `on` is invalid under `parse_options` or is empty. JSON5 `Infinity` and
+/// `NaN` are a [`JSONParseErrorReason::InvalidNumber`] error
+pub fn format_with_options(
+    on: &str,
+    mut parse_options: ParseOptions,
+    options: WriteOptions,
+) -> Result<String, JSONParseError> {
+    parse_options.comment_events = options.comments;
+    let mut iterator = JSONIterator::with_options(on, parse_options);
+    let mut writer = JSONWriter::with_options(String::with_capacity(on.len()), options);
+    while let Some(event) = iterator.next_event() {
+        let event = event?;
+        if writer.in_object() && !matches!(event, JSONEvent::ObjectEnd | JSONEvent::Comment(_)) {
+            if let Some(JSONKey::Slice(key)) = iterator.key_chain().last() {
+                // Writing to a `String` does not fail
+                let _ = writer.key(&unescape(key));
+            }
+        }
+        let _ = match event {
+            JSONEvent::ObjectStart => writer.start_object(),
+            JSONEvent::ObjectEnd => writer.end_object(),
+            JSONEvent::ArrayStart => writer.start_array(),
+            JSONEvent::ArrayEnd => writer.end_array(),
+            JSONEvent::Value(value) => match writer.value(value) {
+                Err(JSONWriteError::InvalidNumber) => {
+                    return Err(JSONParseError {
+                        at: iterator.span().start,
+                        reason: JSONParseErrorReason::InvalidNumber,
+                    });
+                }
+                _ => Ok(()),
+            },
+            JSONEvent::Comment(comment) => {
+                writer.comment(comment.text, comment.kind);
+                continue;
+            }
+        };
+    }
+    iterator.expect_value()?;
+    let _ = writer.finish();
+    Ok(writer.into_inner())
+}